@@ -1,28 +1,77 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{Criterion, criterion_group, criterion_main};
+use ml_dsa::{B32, KeyGen, MlDsa65};
 
 fn benchmark_keygen(c: &mut Criterion) {
+    let xi = B32::default();
     c.bench_function("ml_dsa_keygen", |b| {
-        b.iter(|| {
-            // Placeholder for key generation benchmark
-        })
+        b.iter(|| MlDsa65::key_gen_internal(&xi))
     });
 }
 
 fn benchmark_sign(c: &mut Criterion) {
+    let kp = MlDsa65::key_gen_internal(&B32::default()).unwrap();
+    let sk = kp.signing_key();
     c.bench_function("ml_dsa_sign", |b| {
-        b.iter(|| {
-            // Placeholder for signing benchmark
-        })
+        b.iter(|| sk.sign_deterministic(b"message", b"context"))
+    });
+}
+
+fn benchmark_sign_prepared(c: &mut Criterion) {
+    let kp = MlDsa65::key_gen_internal(&B32::default()).unwrap();
+    let sk = kp.signing_key().prepare();
+    c.bench_function("ml_dsa_sign_prepared", |b| {
+        b.iter(|| sk.sign_deterministic(b"message", b"context"))
+    });
+}
+
+// Signing with every signature checked before release; compare with `ml_dsa_sign_prepared`
+// from a build without the feature
+#[cfg(feature = "verify-after-sign")]
+fn benchmark_sign_verify_after_sign(c: &mut Criterion) {
+    let kp = MlDsa65::key_gen_internal(&B32::default()).unwrap();
+    let sk = kp.signing_key().prepare();
+    c.bench_function("ml_dsa_sign_verify_after_sign", |b| {
+        b.iter(|| sk.sign_deterministic(b"message", b"context"))
     });
 }
 
 fn benchmark_verify(c: &mut Criterion) {
+    let kp = MlDsa65::key_gen_internal(&B32::default()).unwrap();
+    let sig = kp
+        .signing_key()
+        .sign_deterministic(b"message", b"context")
+        .unwrap();
+    let vk = kp.verifying_key();
     c.bench_function("ml_dsa_verify", |b| {
-        b.iter(|| {
-            // Placeholder for verification benchmark
-        })
+        b.iter(|| vk.verify_with_context(b"message", b"context", &sig))
     });
 }
 
-criterion_group!(benches, benchmark_keygen, benchmark_sign, benchmark_verify);
-criterion_main!(benches);
\ No newline at end of file
+fn benchmark_verify_prepared(c: &mut Criterion) {
+    let kp = MlDsa65::key_gen_internal(&B32::default()).unwrap();
+    let sig = kp
+        .signing_key()
+        .sign_deterministic(b"message", b"context")
+        .unwrap();
+    let vk = kp.verifying_key().prepare();
+    c.bench_function("ml_dsa_verify_prepared", |b| {
+        b.iter(|| vk.verify_with_context(b"message", b"context", &sig))
+    });
+}
+
+criterion_group!(
+    benches,
+    benchmark_keygen,
+    benchmark_sign,
+    benchmark_sign_prepared,
+    benchmark_verify,
+    benchmark_verify_prepared
+);
+
+#[cfg(feature = "verify-after-sign")]
+criterion_group!(verify_after_sign_benches, benchmark_sign_verify_after_sign);
+
+#[cfg(not(feature = "verify-after-sign"))]
+criterion_main!(benches);
+#[cfg(feature = "verify-after-sign")]
+criterion_main!(benches, verify_after_sign_benches);
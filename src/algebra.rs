@@ -1,7 +1,341 @@
 //! Algebraic operations for ML-DSA
+//!
+//! This module instantiates the generic `module_lattice` algebra with the ML-DSA base field
+//! (q = 8380417) and adds the ML-DSA-specific operations from FIPS 204 Section 7.4: centered
+//! modular reduction, the infinity norm, `Power2Round`, and `Decompose`/`HighBits`/`LowBits`.
 
-/// Ring element operations
-pub mod ring {
-    /// Ring element
-    pub struct RingElement;
-}
\ No newline at end of file
+pub use crate::module_lattice::algebra::Field;
+pub use crate::module_lattice::util::Truncate;
+
+use hybrid_array::{
+    ArraySize,
+    typenum::{Diff, Shleft, U1, U23, U8192, Unsigned},
+};
+
+use crate::define_field;
+use crate::module_lattice::algebra;
+
+define_field!(
+    /// The ML-DSA base field `Z_q` with `q = 2^23 - 2^13 + 1 = 8380417`
+    BaseField,
+    u32,
+    u64,
+    u128,
+    8380417
+);
+
+/// The integer type used to represent field elements
+pub type Int = <BaseField as Field>::Int;
+
+/// An element of the ML-DSA base field
+pub type Elem = algebra::Elem<BaseField>;
+/// A polynomial in `R_q`
+pub type Polynomial = algebra::Polynomial<BaseField>;
+/// A vector of `K` polynomials in `R_q`
+pub type Vector<K> = algebra::Vector<BaseField, K>;
+/// A polynomial in the NTT domain `T_q`
+pub type NttPolynomial = algebra::NttPolynomial<BaseField>;
+/// A vector of `K` polynomials in `T_q`
+pub type NttVector<K> = algebra::NttVector<BaseField, K>;
+/// A `K x L` matrix of polynomials in `T_q`
+pub type NttMatrix<K, L> = algebra::NttMatrix<BaseField, K, L>;
+
+/// `q - 1 = 2^23 - 2^13` as a type-level integer, from which the `gamma2` values are derived
+pub type QMinus1 = Diff<Shleft<U1, U23>, U8192>;
+
+/// The number of bits dropped from `t` by `Power2Round` (`d` in FIPS 204)
+pub const D: usize = 13;
+
+/// `2^d`, the modulus used by `Power2Round`
+pub type TwoToD = U8192;
+
+/// Barrett reduction modulo a type-level integer `M`, used for the `mod±` operations whose
+/// modulus is not the field prime.
+pub trait BarrettReduce: Unsigned {
+    /// Shift amount for Barrett reduction
+    const SHIFT: usize;
+    /// Multiplier for Barrett reduction, ⌊2^SHIFT / M⌋
+    const MULTIPLIER: u64;
+
+    /// Reduce `x` to the range `[0, M)`
+    fn reduce(x: u32) -> u32 {
+        let m = Self::U64;
+        let x: u64 = x.into();
+        let quotient = (x * Self::MULTIPLIER) >> Self::SHIFT;
+        let remainder = x - quotient * m;
+        if remainder < m {
+            Truncate::truncate(remainder)
+        } else {
+            Truncate::truncate(remainder - m)
+        }
+    }
+}
+
+impl<M> BarrettReduce for M
+where
+    M: Unsigned,
+{
+    // With a 32-bit shift the estimated quotient is off by at most one for any `u32` input, so
+    // the single conditional subtraction in `reduce` suffices for every modulus.
+    const SHIFT: usize = 32;
+    #[allow(clippy::integer_division_remainder_used)]
+    const MULTIPLIER: u64 = (1 << Self::SHIFT) / M::U64;
+}
+
+/// Splitting of a field element into high and low parts with respect to `2 * gamma2`
+pub trait Decompose {
+    /// Return `(r1, r0)` such that `r = r1 * 2 * gamma2 + r0` with `r0` centered
+    fn decompose<TwoGamma2: Unsigned>(self) -> (Elem, Elem);
+}
+
+impl Decompose for Elem {
+    // Algorithm 36 Decompose
+    fn decompose<TwoGamma2: Unsigned>(self) -> (Elem, Elem) {
+        let r_plus = self;
+        let r0 = r_plus.mod_plus_minus::<TwoGamma2>();
+
+        if r_plus - r0 == Elem::new(BaseField::Q - 1) {
+            (Elem::new(0), r0 - Elem::new(1))
+        } else {
+            let mut r1 = r_plus - r0;
+            r1.0 /= TwoGamma2::U32;
+            (r1, r0)
+        }
+    }
+}
+
+/// ML-DSA-specific operations on field elements, polynomials, and vectors
+#[allow(clippy::module_name_repetitions)]
+pub trait AlgebraExt: Sized {
+    /// Centered reduction `r mod± M`, with the result represented as a field element
+    fn mod_plus_minus<M: Unsigned>(&self) -> Self;
+    /// The infinity norm, i.e., the largest absolute value of a centered coefficient
+    fn infinity_norm(&self) -> Int;
+    /// Split into `(r1, r0)` with `r = r1 * 2^d + r0` (Algorithm 35 Power2Round)
+    fn power2round(&self) -> (Self, Self);
+    /// The high part of the decomposition (Algorithm 37 HighBits)
+    fn high_bits<TwoGamma2: Unsigned>(&self) -> Self;
+    /// The low part of the decomposition (Algorithm 38 LowBits)
+    fn low_bits<TwoGamma2: Unsigned>(&self) -> Self;
+}
+
+impl AlgebraExt for Elem {
+    fn mod_plus_minus<M: Unsigned>(&self) -> Self {
+        let raw_mod = Elem::new(M::reduce(self.0));
+        if raw_mod.0 <= M::U32 >> 1 {
+            raw_mod
+        } else {
+            raw_mod - Elem::new(M::U32)
+        }
+    }
+
+    // FIPS 204 defines the infinity norm in terms of the centered representative, so values
+    // above (q-1)/2 are interpreted as negative.
+    fn infinity_norm(&self) -> Int {
+        if self.0 <= BaseField::Q >> 1 {
+            self.0
+        } else {
+            BaseField::Q - self.0
+        }
+    }
+
+    // Algorithm 35 Power2Round
+    //
+    // The input is already reduced mod q, so `r+` is just the element itself.
+    fn power2round(&self) -> (Self, Self) {
+        let r_plus = *self;
+        let r0 = r_plus.mod_plus_minus::<TwoToD>();
+        let r1 = Elem::new((r_plus - r0).0 >> D);
+        (r1, r0)
+    }
+
+    // Algorithm 37 HighBits
+    fn high_bits<TwoGamma2: Unsigned>(&self) -> Self {
+        self.decompose::<TwoGamma2>().0
+    }
+
+    // Algorithm 38 LowBits
+    fn low_bits<TwoGamma2: Unsigned>(&self) -> Self {
+        self.decompose::<TwoGamma2>().1
+    }
+}
+
+impl AlgebraExt for Polynomial {
+    fn mod_plus_minus<M: Unsigned>(&self) -> Self {
+        Self(self.0.iter().map(AlgebraExt::mod_plus_minus::<M>).collect())
+    }
+
+    fn infinity_norm(&self) -> Int {
+        self.0
+            .iter()
+            .map(AlgebraExt::infinity_norm)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn power2round(&self) -> (Self, Self) {
+        let mut r1 = Self::default();
+        let mut r0 = Self::default();
+
+        for (i, x) in self.0.iter().enumerate() {
+            (r1.0[i], r0.0[i]) = x.power2round();
+        }
+
+        (r1, r0)
+    }
+
+    fn high_bits<TwoGamma2: Unsigned>(&self) -> Self {
+        Self(self.0.iter().map(AlgebraExt::high_bits::<TwoGamma2>).collect())
+    }
+
+    fn low_bits<TwoGamma2: Unsigned>(&self) -> Self {
+        Self(self.0.iter().map(AlgebraExt::low_bits::<TwoGamma2>).collect())
+    }
+}
+
+impl<K: ArraySize> AlgebraExt for Vector<K> {
+    fn mod_plus_minus<M: Unsigned>(&self) -> Self {
+        Self(self.0.iter().map(AlgebraExt::mod_plus_minus::<M>).collect())
+    }
+
+    fn infinity_norm(&self) -> Int {
+        self.0
+            .iter()
+            .map(AlgebraExt::infinity_norm)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn power2round(&self) -> (Self, Self) {
+        let mut r1 = Self::default();
+        let mut r0 = Self::default();
+
+        for (i, x) in self.0.iter().enumerate() {
+            (r1.0[i], r0.0[i]) = x.power2round();
+        }
+
+        (r1, r0)
+    }
+
+    fn high_bits<TwoGamma2: Unsigned>(&self) -> Self {
+        Self(self.0.iter().map(AlgebraExt::high_bits::<TwoGamma2>).collect())
+    }
+
+    fn low_bits<TwoGamma2: Unsigned>(&self) -> Self {
+        Self(self.0.iter().map(AlgebraExt::low_bits::<TwoGamma2>).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hybrid_array::typenum::{Quot, U2, U10, U16, U44};
+
+    // 2 * gamma2 for ML-DSA-44 ((q-1)/44) and for ML-DSA-65/87 ((q-1)/16)
+    type TwoGamma2Small = Quot<QMinus1, U44>;
+    type TwoGamma2Large = Quot<QMinus1, U16>;
+
+    const Q: u32 = BaseField::Q;
+
+    // Reference `r mod± m` computed on signed integers
+    fn centered(r: u32, m: u32) -> i64 {
+        let r0 = i64::from(r % m);
+        if r0 <= i64::from(m / 2) {
+            r0
+        } else {
+            r0 - i64::from(m)
+        }
+    }
+
+    fn to_signed(x: Elem) -> i64 {
+        if x.0 <= Q / 2 {
+            i64::from(x.0)
+        } else {
+            i64::from(x.0) - i64::from(Q)
+        }
+    }
+
+    #[test]
+    fn barrett_reduce() {
+        for x in [0, 1, 9, 10, 11, 12345, Q - 1, u32::MAX] {
+            assert_eq!(<U10 as BarrettReduce>::reduce(x), x % 10);
+            assert_eq!(<TwoGamma2Small as BarrettReduce>::reduce(x), x % 190464);
+            assert_eq!(<TwoGamma2Large as BarrettReduce>::reduce(x), x % 523776);
+            assert_eq!(<TwoToD as BarrettReduce>::reduce(x), x % 8192);
+        }
+    }
+
+    #[test]
+    fn mod_plus_minus() {
+        assert_eq!(Elem::new(0).mod_plus_minus::<U10>(), Elem::new(0));
+        assert_eq!(Elem::new(5).mod_plus_minus::<U10>(), Elem::new(5));
+        assert_eq!(Elem::new(6).mod_plus_minus::<U10>(), Elem::new(Q - 4));
+        assert_eq!(Elem::new(1).mod_plus_minus::<U2>(), Elem::new(1));
+        assert_eq!(Elem::new(2).mod_plus_minus::<U2>(), Elem::new(0));
+
+        for r in (0..Q).step_by(997) {
+            let expected = centered(r, 8192);
+            assert_eq!(to_signed(Elem::new(r).mod_plus_minus::<TwoToD>()), expected);
+        }
+    }
+
+    #[test]
+    fn infinity_norm() {
+        assert_eq!(Elem::new(0).infinity_norm(), 0);
+        assert_eq!(Elem::new(7).infinity_norm(), 7);
+        assert_eq!(Elem::new(Q - 7).infinity_norm(), 7);
+        assert_eq!(Elem::new(Q / 2).infinity_norm(), Q / 2);
+
+        let mut p = Polynomial::default();
+        p.0[3] = Elem::new(100);
+        p.0[200] = Elem::new(Q - 250);
+        assert_eq!(p.infinity_norm(), 250);
+
+        let mut v = Vector::<U2>::default();
+        v.0[1] = p;
+        assert_eq!(v.infinity_norm(), 250);
+    }
+
+    #[test]
+    fn power2round() {
+        for r in (0..Q).step_by(101) {
+            let (r1, r0) = Elem::new(r).power2round();
+            let r0 = to_signed(r0);
+
+            assert!(r0 > -(1 << (D - 1)) && r0 <= 1 << (D - 1));
+            assert!(r1.0 <= (Q - 1) >> D);
+            assert_eq!(i64::from(r1.0) * (1 << D) + r0, i64::from(r));
+        }
+    }
+
+    fn check_decompose<TwoGamma2: Unsigned>() {
+        let two_gamma2 = TwoGamma2::U32;
+        let m = (Q - 1) / two_gamma2;
+
+        for r in (0..Q).step_by(61).chain([Q - 1, Q - 2, Q - two_gamma2 / 2]) {
+            let x = Elem::new(r);
+            let (r1, r0) = x.decompose::<TwoGamma2>();
+            let r0 = to_signed(r0);
+
+            assert!(r1.0 < m);
+            assert!(r0 >= -i64::from(two_gamma2 / 2) && r0 <= i64::from(two_gamma2 / 2));
+
+            let lhs = (i64::from(r1.0) * i64::from(two_gamma2) + r0).rem_euclid(i64::from(Q));
+            assert_eq!(lhs, i64::from(r));
+
+            assert_eq!(x.high_bits::<TwoGamma2>().0, r1.0);
+            assert_eq!(to_signed(x.low_bits::<TwoGamma2>()), r0);
+        }
+    }
+
+    #[test]
+    fn decompose() {
+        check_decompose::<TwoGamma2Small>();
+        check_decompose::<TwoGamma2Large>();
+
+        // The wrap-around case where r+ - r0 = q - 1
+        let (r1, r0) = Elem::new(Q - 1).decompose::<TwoGamma2Small>();
+        assert_eq!(r1.0, 0);
+        assert_eq!(to_signed(r0), -1);
+    }
+}
@@ -0,0 +1,507 @@
+//! Parameter sets selected at runtime
+//!
+//! The key and signature types of this crate are generic over the parameter set, which is the
+//! right choice when the parameter set is fixed at compile time.  When it is only known at
+//! runtime, e.g., from a configuration file, an algorithm identifier, or the length of an
+//! encoded key, the enums in this module wrap the three parameter sets and dispatch to the
+//! matching implementation.  Operations that combine values for different parameter sets, such
+//! as verifying an ML-DSA-65 signature with an ML-DSA-44 key, fail with an error.
+
+use hybrid_array::typenum::Unsigned;
+use signature::{Error, Keypair, SignatureEncoding, Signer, Verifier};
+
+#[cfg(feature = "pkcs8")]
+use const_oid::{AssociatedOid, ObjectIdentifier};
+
+#[cfg(feature = "rand_core")]
+use rand_core::{CryptoRng, TryCryptoRng};
+
+use crate::param::{SignatureParams, SigningKeyParams, VerifyingKeyParams};
+use crate::util::B32;
+use crate::{
+    KeyGen, MlDsa44, MlDsa65, MlDsa87, MlDsaError, MlDsaParams, Signature, SigningKey,
+    VerifyingKey, ensure_operational,
+};
+
+/// An ML-DSA parameter set, identified at runtime
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// ML-DSA-44
+    MlDsa44,
+    /// ML-DSA-65
+    MlDsa65,
+    /// ML-DSA-87
+    MlDsa87,
+}
+
+impl Algorithm {
+    /// All the ML-DSA parameter sets
+    pub const ALL: [Self; 3] = [Self::MlDsa44, Self::MlDsa65, Self::MlDsa87];
+
+    /// The name of the parameter set, e.g., `"ML-DSA-44"`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MlDsa44 => "ML-DSA-44",
+            Self::MlDsa65 => "ML-DSA-65",
+            Self::MlDsa87 => "ML-DSA-87",
+        }
+    }
+
+    /// The size of an encoded signing key
+    pub fn signing_key_size(&self) -> usize {
+        match self {
+            Self::MlDsa44 => <MlDsa44 as SigningKeyParams>::SigningKeySize::USIZE,
+            Self::MlDsa65 => <MlDsa65 as SigningKeyParams>::SigningKeySize::USIZE,
+            Self::MlDsa87 => <MlDsa87 as SigningKeyParams>::SigningKeySize::USIZE,
+        }
+    }
+
+    /// The size of an encoded verifying key
+    pub fn verifying_key_size(&self) -> usize {
+        match self {
+            Self::MlDsa44 => <MlDsa44 as VerifyingKeyParams>::VerifyingKeySize::USIZE,
+            Self::MlDsa65 => <MlDsa65 as VerifyingKeyParams>::VerifyingKeySize::USIZE,
+            Self::MlDsa87 => <MlDsa87 as VerifyingKeyParams>::VerifyingKeySize::USIZE,
+        }
+    }
+
+    /// The size of an encoded signature
+    pub fn signature_size(&self) -> usize {
+        match self {
+            Self::MlDsa44 => <MlDsa44 as SignatureParams>::SignatureSize::USIZE,
+            Self::MlDsa65 => <MlDsa65 as SignatureParams>::SignatureSize::USIZE,
+            Self::MlDsa87 => <MlDsa87 as SignatureParams>::SignatureSize::USIZE,
+        }
+    }
+
+    /// The parameter set whose encoded signing keys are `len` bytes long
+    pub fn from_signing_key_size(len: usize) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.signing_key_size() == len)
+    }
+
+    /// The parameter set whose encoded verifying keys are `len` bytes long
+    pub fn from_verifying_key_size(len: usize) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.verifying_key_size() == len)
+    }
+
+    /// The parameter set whose encoded signatures are `len` bytes long
+    pub fn from_signature_size(len: usize) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.signature_size() == len)
+    }
+
+    /// The algorithm identifier of the parameter set (`id-ml-dsa-44` etc.)
+    #[cfg(feature = "pkcs8")]
+    pub fn oid(&self) -> ObjectIdentifier {
+        match self {
+            Self::MlDsa44 => MlDsa44::OID,
+            Self::MlDsa65 => MlDsa65::OID,
+            Self::MlDsa87 => MlDsa87::OID,
+        }
+    }
+
+    /// The parameter set with the algorithm identifier `oid`
+    #[cfg(feature = "pkcs8")]
+    pub fn from_oid(oid: ObjectIdentifier) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.oid() == oid)
+    }
+
+    /// Deterministically derive a key pair from the seed `xi` (Algorithm 6
+    /// ML-DSA.KeyGen_internal)
+    ///
+    /// With the `self-test` feature, returns an error if a self-test fails.
+    pub fn key_gen_internal(&self, xi: &B32) -> Result<(AnySigningKey, AnyVerifyingKey), Error> {
+        Ok(match self {
+            Self::MlDsa44 => split(MlDsa44::key_gen_internal(xi)?.into_parts()),
+            Self::MlDsa65 => split(MlDsa65::key_gen_internal(xi)?.into_parts()),
+            Self::MlDsa87 => split(MlDsa87::key_gen_internal(xi)?.into_parts()),
+        })
+    }
+
+    /// Generate a key pair from a fresh random seed (Algorithm 1 ML-DSA.KeyGen)
+    ///
+    /// Returns an error if `rng` fails, or, with the `self-test` feature, if a self-test fails.
+    #[cfg(feature = "rand_core")]
+    pub fn key_gen<R: TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(AnySigningKey, AnyVerifyingKey), Error> {
+        Ok(match self {
+            Self::MlDsa44 => split(MlDsa44::key_gen(rng)?.into_parts()),
+            Self::MlDsa65 => split(MlDsa65::key_gen(rng)?.into_parts()),
+            Self::MlDsa87 => split(MlDsa87::key_gen(rng)?.into_parts()),
+        })
+    }
+}
+
+fn split<P>((sk, vk): (SigningKey<P>, VerifyingKey<P>)) -> (AnySigningKey, AnyVerifyingKey)
+where
+    P: MlDsaParams,
+    AnySigningKey: From<SigningKey<P>>,
+    AnyVerifyingKey: From<VerifyingKey<P>>,
+{
+    (sk.into(), vk.into())
+}
+
+// Decode `bytes` as the variant for `alg` of one of the enums below
+macro_rules! decode_as {
+    ($any:ident, $inner:ident, $alg:expr, $bytes:expr) => {
+        match $alg {
+            Algorithm::MlDsa44 => $inner::<MlDsa44>::try_from($bytes).map($any::MlDsa44),
+            Algorithm::MlDsa65 => $inner::<MlDsa65>::try_from($bytes).map($any::MlDsa65),
+            Algorithm::MlDsa87 => $inner::<MlDsa87>::try_from($bytes).map($any::MlDsa87),
+        }
+    };
+}
+
+// Implement the parts of the API that are the same for all three enums
+macro_rules! define_any {
+    ($(#[$meta:meta])* $any:ident, $inner:ident, $size:ident) => {
+        $(#[$meta])*
+        // The variants hold the fixed-size types directly, like the generic API does, so
+        // that matching on a value yields the familiar types without an allocation.
+        #[allow(clippy::large_enum_variant)]
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum $any {
+            /// ML-DSA-44
+            MlDsa44($inner<MlDsa44>),
+            /// ML-DSA-65
+            MlDsa65($inner<MlDsa65>),
+            /// ML-DSA-87
+            MlDsa87($inner<MlDsa87>),
+        }
+
+        impl $any {
+            /// The parameter set of the value
+            pub fn algorithm(&self) -> Algorithm {
+                match self {
+                    Self::MlDsa44(_) => Algorithm::MlDsa44,
+                    Self::MlDsa65(_) => Algorithm::MlDsa65,
+                    Self::MlDsa87(_) => Algorithm::MlDsa87,
+                }
+            }
+
+            /// Decode a value whose parameter set is identified by the length of `bytes`
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+                let alg = Algorithm::$size(bytes.len()).ok_or(MlDsaError::InvalidLength)?;
+                decode_as!($any, $inner, alg, bytes)
+            }
+
+            /// Decode a value for the parameter set `alg`
+            ///
+            /// Returns an error if `bytes` does not have the length required by `alg`.
+            pub fn from_bytes_with_algorithm(alg: Algorithm, bytes: &[u8]) -> Result<Self, Error> {
+                decode_as!($any, $inner, alg, bytes)
+            }
+
+            /// Decode a value for the parameter set with the algorithm identifier `oid`
+            ///
+            /// Returns an error if `oid` is not an ML-DSA algorithm identifier or `bytes` does
+            /// not have the length required by that parameter set.
+            #[cfg(feature = "pkcs8")]
+            pub fn from_bytes_with_oid(oid: ObjectIdentifier, bytes: &[u8]) -> Result<Self, Error> {
+                let alg = Algorithm::from_oid(oid).ok_or(MlDsaError::UnsupportedAlgorithm)?;
+                decode_as!($any, $inner, alg, bytes)
+            }
+
+            /// Encode the value as bytes
+            pub fn to_vec(&self) -> Vec<u8> {
+                match self {
+                    Self::MlDsa44(x) => x.encode().to_vec(),
+                    Self::MlDsa65(x) => x.encode().to_vec(),
+                    Self::MlDsa87(x) => x.encode().to_vec(),
+                }
+            }
+        }
+
+        impl TryFrom<&[u8]> for $any {
+            type Error = Error;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+                Self::from_bytes(bytes)
+            }
+        }
+
+        impl From<$inner<MlDsa44>> for $any {
+            fn from(x: $inner<MlDsa44>) -> Self {
+                Self::MlDsa44(x)
+            }
+        }
+
+        impl From<$inner<MlDsa65>> for $any {
+            fn from(x: $inner<MlDsa65>) -> Self {
+                Self::MlDsa65(x)
+            }
+        }
+
+        impl From<$inner<MlDsa87>> for $any {
+            fn from(x: $inner<MlDsa87>) -> Self {
+                Self::MlDsa87(x)
+            }
+        }
+    };
+}
+
+define_any!(
+    /// A signing key for a parameter set chosen at runtime
+    AnySigningKey,
+    SigningKey,
+    from_signing_key_size
+);
+
+define_any!(
+    /// A verifying key for a parameter set chosen at runtime
+    AnyVerifyingKey,
+    VerifyingKey,
+    from_verifying_key_size
+);
+
+define_any!(
+    /// A signature for a parameter set chosen at runtime
+    AnySignature,
+    Signature,
+    from_signature_size
+);
+
+impl AnySigningKey {
+    /// Derive the verifying key that corresponds to this signing key
+    pub fn verifying_key(&self) -> AnyVerifyingKey {
+        match self {
+            Self::MlDsa44(sk) => sk.verifying_key().into(),
+            Self::MlDsa65(sk) => sk.verifying_key().into(),
+            Self::MlDsa87(sk) => sk.verifying_key().into(),
+        }
+    }
+
+    /// Sign the message `m` with the context string `ctx`, deterministically
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    pub fn sign_deterministic(&self, m: &[u8], ctx: &[u8]) -> Result<AnySignature, Error> {
+        match self {
+            Self::MlDsa44(sk) => sk.sign_deterministic(m, ctx).map(Into::into),
+            Self::MlDsa65(sk) => sk.sign_deterministic(m, ctx).map(Into::into),
+            Self::MlDsa87(sk) => sk.sign_deterministic(m, ctx).map(Into::into),
+        }
+    }
+
+    /// Sign the message `m` with the context string `ctx`, using fresh randomness from `rng`
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    #[cfg(feature = "rand_core")]
+    pub fn sign_randomized<R: CryptoRng + ?Sized>(
+        &self,
+        m: &[u8],
+        ctx: &[u8],
+        rng: &mut R,
+    ) -> Result<AnySignature, Error> {
+        match self {
+            Self::MlDsa44(sk) => sk.sign_randomized(m, ctx, rng).map(Into::into),
+            Self::MlDsa65(sk) => sk.sign_randomized(m, ctx, rng).map(Into::into),
+            Self::MlDsa87(sk) => sk.sign_randomized(m, ctx, rng).map(Into::into),
+        }
+    }
+}
+
+impl AnyVerifyingKey {
+    /// Verify a signature on the message `m` with the context string `ctx`
+    ///
+    /// Returns an error if the signature is invalid, belongs to a different parameter set than
+    /// the key, or `ctx` is longer than 255 bytes, or, with the `self-test` feature, if a
+    /// self-test has failed.
+    pub fn verify_with_context(
+        &self,
+        m: &[u8],
+        ctx: &[u8],
+        sigma: &AnySignature,
+    ) -> Result<(), Error> {
+        ensure_operational()?;
+        match (self, sigma) {
+            (Self::MlDsa44(vk), AnySignature::MlDsa44(sig)) => vk.verify_with_context(m, ctx, sig),
+            (Self::MlDsa65(vk), AnySignature::MlDsa65(sig)) => vk.verify_with_context(m, ctx, sig),
+            (Self::MlDsa87(vk), AnySignature::MlDsa87(sig)) => vk.verify_with_context(m, ctx, sig),
+            _ => Err(MlDsaError::KeyMismatch.into()),
+        }
+    }
+
+    /// Decode and verify a signature on the message `m` with the context string `ctx`
+    ///
+    /// The signature is decoded for the parameter set of the key, so a signature of the wrong
+    /// length is rejected.
+    pub fn verify_bytes(&self, m: &[u8], ctx: &[u8], sigma: &[u8]) -> Result<(), Error> {
+        ensure_operational()?;
+        let sigma = AnySignature::from_bytes_with_algorithm(self.algorithm(), sigma)?;
+        self.verify_with_context(m, ctx, &sigma)
+    }
+}
+
+/// Deterministic signing with an empty context string
+impl Signer<AnySignature> for AnySigningKey {
+    fn try_sign(&self, msg: &[u8]) -> Result<AnySignature, Error> {
+        self.sign_deterministic(msg, &[])
+    }
+}
+
+impl Keypair for AnySigningKey {
+    type VerifyingKey = AnyVerifyingKey;
+
+    fn verifying_key(&self) -> AnyVerifyingKey {
+        AnySigningKey::verifying_key(self)
+    }
+}
+
+/// Verification with an empty context string
+impl Verifier<AnySignature> for AnyVerifyingKey {
+    fn verify(&self, msg: &[u8], signature: &AnySignature) -> Result<(), Error> {
+        self.verify_with_context(msg, &[], signature)
+    }
+}
+
+impl From<AnySignature> for Vec<u8> {
+    fn from(sig: AnySignature) -> Self {
+        sig.to_vec()
+    }
+}
+
+impl SignatureEncoding for AnySignature {
+    type Repr = Vec<u8>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MSG: &[u8] = b"message";
+
+    #[test]
+    fn sizes() {
+        let sizes: Vec<_> = Algorithm::ALL
+            .iter()
+            .map(|a| {
+                (
+                    a.verifying_key_size(),
+                    a.signing_key_size(),
+                    a.signature_size(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sizes,
+            [(1312, 2560, 2420), (1952, 4032, 3309), (2592, 4896, 4627)]
+        );
+
+        for alg in Algorithm::ALL {
+            assert_eq!(
+                Algorithm::from_signing_key_size(alg.signing_key_size()),
+                Some(alg)
+            );
+            assert_eq!(
+                Algorithm::from_verifying_key_size(alg.verifying_key_size()),
+                Some(alg)
+            );
+            assert_eq!(
+                Algorithm::from_signature_size(alg.signature_size()),
+                Some(alg)
+            );
+        }
+        assert_eq!(Algorithm::from_signature_size(2421), None);
+    }
+
+    #[test]
+    fn fallible_key_gen() {
+        for alg in Algorithm::ALL {
+            let xi = B32::from([9; 32]);
+            let (sk, vk) = alg.key_gen_internal(&xi).unwrap();
+            assert_eq!(sk.algorithm(), alg);
+            assert_eq!(sk.verifying_key(), vk);
+        }
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn fallible_random_key_gen() {
+        let mut rng = rand::rng();
+        let (sk, vk) = Algorithm::MlDsa87.key_gen(&mut rng).unwrap();
+        assert_eq!(vk.algorithm(), Algorithm::MlDsa87);
+        assert!(vk.verify(MSG, &sk.sign(MSG)).is_ok());
+    }
+
+    #[test]
+    fn detect_by_length() {
+        for alg in Algorithm::ALL {
+            let (sk, vk) = alg.key_gen_internal(&B32::from([9; 32])).unwrap();
+            assert_eq!(sk.algorithm(), alg);
+            assert_eq!(sk.verifying_key(), vk);
+
+            let sk = AnySigningKey::from_bytes(&sk.to_vec()).unwrap();
+            let vk = AnyVerifyingKey::from_bytes(&vk.to_vec()).unwrap();
+            assert_eq!(sk.algorithm(), alg);
+            assert_eq!(vk.algorithm(), alg);
+
+            let sig = sk.sign_deterministic(MSG, b"ctx").unwrap();
+            let sig = AnySignature::from_bytes(&sig.to_vec()).unwrap();
+            assert_eq!(sig.algorithm(), alg);
+            assert!(vk.verify_with_context(MSG, b"ctx", &sig).is_ok());
+            assert!(vk.verify_bytes(MSG, b"ctx", &sig.to_vec()).is_ok());
+            assert!(vk.verify(MSG, &sk.sign(MSG)).is_ok());
+        }
+
+        assert!(AnySigningKey::from_bytes(&[0; 100]).is_err());
+        assert!(AnyVerifyingKey::from_bytes(&[]).is_err());
+        assert!(AnySignature::from_bytes(&[0; 2421]).is_err());
+    }
+
+    #[test]
+    fn reject_mismatched_parameter_sets() {
+        let (sk44, vk44) = Algorithm::MlDsa44
+            .key_gen_internal(&B32::from([1; 32]))
+            .unwrap();
+        let (sk65, _) = Algorithm::MlDsa65
+            .key_gen_internal(&B32::from([1; 32]))
+            .unwrap();
+
+        let sig44 = sk44.sign_deterministic(MSG, &[]).unwrap();
+        let sig65 = sk65.sign_deterministic(MSG, &[]).unwrap();
+        assert!(vk44.verify_with_context(MSG, &[], &sig44).is_ok());
+        assert!(vk44.verify_with_context(MSG, &[], &sig65).is_err());
+        assert!(vk44.verify_bytes(MSG, &[], &sig65.to_vec()).is_err());
+
+        // Decoding with an explicit parameter set checks the length
+        let bytes = vk44.to_vec();
+        assert!(AnyVerifyingKey::from_bytes_with_algorithm(Algorithm::MlDsa44, &bytes).is_ok());
+        assert!(AnyVerifyingKey::from_bytes_with_algorithm(Algorithm::MlDsa65, &bytes).is_err());
+    }
+
+    #[cfg(feature = "pkcs8")]
+    #[test]
+    fn detect_by_oid() {
+        assert_eq!(
+            Algorithm::MlDsa44.oid().to_string(),
+            "2.16.840.1.101.3.4.3.17"
+        );
+        assert_eq!(
+            Algorithm::MlDsa65.oid().to_string(),
+            "2.16.840.1.101.3.4.3.18"
+        );
+        assert_eq!(
+            Algorithm::MlDsa87.oid().to_string(),
+            "2.16.840.1.101.3.4.3.19"
+        );
+
+        let (_, vk) = Algorithm::MlDsa65
+            .key_gen_internal(&B32::from([2; 32]))
+            .unwrap();
+        let bytes = vk.to_vec();
+        let oid = Algorithm::MlDsa65.oid();
+        assert_eq!(
+            AnyVerifyingKey::from_bytes_with_oid(oid, &bytes).unwrap(),
+            vk
+        );
+
+        // A known OID with a key of the wrong length, or an unknown OID
+        assert!(AnyVerifyingKey::from_bytes_with_oid(Algorithm::MlDsa87.oid(), &bytes).is_err());
+        let sha256 = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+        assert_eq!(Algorithm::from_oid(sha256), None);
+        assert!(AnyVerifyingKey::from_bytes_with_oid(sha256, &bytes).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Verification of many signatures at once
+//!
+//! A batch is a list of (key, message, context, signature) tuples.  Each distinct verifying key
+//! in the batch is prepared once (see [`PreparedVerifyingKey`]) and shared by all the items that
+//! use it, so a batch signed by a handful of keys pays for `ExpandA` only a handful of times.
+//! With the `rayon` feature, the keys are prepared and the items verified in parallel.
+//!
+//! ML-DSA has no algebraic shortcut for batch verification: every item is checked on its own,
+//! and the result for each item is exactly what [`VerifyingKey::verify_with_context`] returns.
+
+use std::collections::HashMap;
+
+use signature::Error;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::param::EncodedVerifyingKey;
+use crate::{MlDsaParams, PreparedVerifyingKey, Signature, VerifyingKey};
+
+/// One signature to be checked as part of a batch
+#[derive(Clone, Debug)]
+pub struct BatchItem<'a, P: MlDsaParams> {
+    /// The key the signature should verify under
+    pub key: &'a VerifyingKey<P>,
+    /// The signed message
+    pub msg: &'a [u8],
+    /// The context string the message was signed with
+    pub ctx: &'a [u8],
+    /// The signature
+    pub sig: &'a Signature<P>,
+}
+
+impl<'a, P: MlDsaParams> BatchItem<'a, P> {
+    /// Create a batch item
+    pub fn new(
+        key: &'a VerifyingKey<P>,
+        msg: &'a [u8],
+        ctx: &'a [u8],
+        sig: &'a Signature<P>,
+    ) -> Self {
+        Self { key, msg, ctx, sig }
+    }
+}
+
+// Prepare each distinct key once.  Returns the prepared keys and, for each item, the index of
+// its prepared key.
+fn prepare_keys<P: MlDsaParams>(
+    items: &[BatchItem<'_, P>],
+) -> (Vec<PreparedVerifyingKey<P>>, Vec<usize>) {
+    let mut distinct: HashMap<EncodedVerifyingKey<P>, usize> = HashMap::new();
+    let mut keys = Vec::new();
+    let index = items
+        .iter()
+        .map(|item| {
+            *distinct.entry(item.key.encode()).or_insert_with(|| {
+                keys.push(item.key);
+                keys.len() - 1
+            })
+        })
+        .collect();
+
+    #[cfg(feature = "rayon")]
+    let keys = keys.into_par_iter().map(VerifyingKey::prepare).collect();
+    #[cfg(not(feature = "rayon"))]
+    let keys = keys.into_iter().map(VerifyingKey::prepare).collect();
+
+    (keys, index)
+}
+
+/// Verify every item of a batch, and report the result for each item in order
+///
+/// An item fails if its signature is invalid or its context string is longer than 255 bytes.
+/// With the `self-test` feature, every item fails if a self-test has failed.
+pub fn verify_batch<P: MlDsaParams>(items: &[BatchItem<'_, P>]) -> Vec<Result<(), Error>> {
+    let (keys, index) = prepare_keys(items);
+    let verify = |(item, &k): (&BatchItem<'_, P>, &usize)| {
+        keys[k].verify_with_context(item.msg, item.ctx, item.sig)
+    };
+
+    #[cfg(feature = "rayon")]
+    let results = items.par_iter().zip(&index).map(verify).collect();
+    #[cfg(not(feature = "rayon"))]
+    let results = items.iter().zip(&index).map(verify).collect();
+
+    results
+}
+
+/// Verify every item of a batch, and report whether all of them are valid
+///
+/// Stops at the first invalid item found.  An empty batch is valid.
+pub fn verify_batch_all<P: MlDsaParams>(items: &[BatchItem<'_, P>]) -> bool {
+    let (keys, index) = prepare_keys(items);
+    let verify = |(item, &k): (&BatchItem<'_, P>, &usize)| {
+        keys[k]
+            .verify_with_context(item.msg, item.ctx, item.sig)
+            .is_ok()
+    };
+
+    #[cfg(feature = "rayon")]
+    let valid = items.par_iter().zip(&index).all(verify);
+    #[cfg(not(feature = "rayon"))]
+    let valid = items.iter().zip(&index).all(verify);
+
+    valid
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::B32;
+    use crate::{KeyGen, KeyPair, MlDsa44, MlDsa65};
+
+    fn keys<P: MlDsaParams>(n: u8) -> Vec<KeyPair<P>> {
+        (0..n)
+            .map(|i| P::key_gen_internal(&B32::from([i; 32])).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn batch_matches_individual() {
+        let kps = keys::<MlDsa44>(3);
+        let msgs: Vec<Vec<u8>> = (0..12u8).map(|i| vec![i; usize::from(i)]).collect();
+
+        // Items cycle through the keys, so each key is shared by several items
+        let sigs: Vec<_> = msgs
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let sk = kps[i % 3].signing_key();
+                sk.sign_deterministic(m, b"log").unwrap()
+            })
+            .collect();
+
+        let mut items: Vec<_> = msgs
+            .iter()
+            .zip(&sigs)
+            .enumerate()
+            .map(|(i, (m, s))| BatchItem::new(kps[i % 3].verifying_key(), m, b"log", s))
+            .collect();
+        assert!(verify_batch(&items).iter().all(Result::is_ok));
+        assert!(verify_batch_all(&items));
+
+        // Break a few items in different ways
+        let too_long = [0; 256];
+        items[1].ctx = b"other";
+        items[4].key = kps[0].verifying_key();
+        items[7].msg = b"tampered";
+        items[9].ctx = &too_long;
+
+        let results = verify_batch(&items);
+        for (i, (item, result)) in items.iter().zip(&results).enumerate() {
+            let expected = item.key.verify_with_context(item.msg, item.ctx, item.sig);
+            assert_eq!(result.is_ok(), expected.is_ok());
+            assert_eq!(result.is_ok(), ![1, 4, 7, 9].contains(&i));
+        }
+        assert!(!verify_batch_all(&items));
+    }
+
+    #[test]
+    fn shares_equal_keys() {
+        // Distinct but equal key objects are prepared once
+        let kp = MlDsa65::key_gen_internal(&B32::from([1; 32])).unwrap();
+        let copies = [kp.verifying_key().clone(), kp.verifying_key().clone()];
+        let sig = kp.signing_key().sign_deterministic(b"m", &[]).unwrap();
+
+        let items: Vec<_> = (0..4)
+            .map(|i| BatchItem::new(&copies[i % 2], b"m", &[], &sig))
+            .collect();
+        let (prepared, index) = prepare_keys(&items);
+        assert_eq!(prepared.len(), 1);
+        assert_eq!(index, [0, 0, 0, 0]);
+        assert!(verify_batch_all(&items));
+    }
+
+    #[test]
+    fn empty_batch() {
+        assert!(verify_batch::<MlDsa44>(&[]).is_empty());
+        assert!(verify_batch_all::<MlDsa44>(&[]));
+    }
+}
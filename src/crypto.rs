@@ -1,7 +1,102 @@
 //! Cryptographic functions for ML-DSA
+//!
+//! All hashing and sampling in ML-DSA is built on the SHAKE extendable-output functions.  The
+//! `ShakeState` and `ShakeReader` types wrap a SHAKE sponge with the incremental absorb/squeeze
+//! interface used throughout FIPS 204 (Section 3.7), so that every XOF call in the crate goes
+//! through a single code path.  FIPS 204 never absorbs after squeezing, and the two types make
+//! that impossible: squeezing consumes the absorbing state.
 
-/// Cryptographic hash functions
-pub mod hash {
-    /// Hash function implementations
-    pub struct Hash;
-}
\ No newline at end of file
+use hybrid_array::{Array, ArraySize};
+use sha3::{
+    Shake128, Shake256,
+    digest::{ExtendableOutput, XofReader},
+};
+
+/// An incremental SHAKE computation that is absorbing input
+#[derive(Clone, Default)]
+pub struct ShakeState<Shake>(Shake);
+
+impl<Shake: ExtendableOutput> ShakeState<Shake> {
+    /// Absorb `input` into the sponge (`H.Absorb` / `G.Absorb`)
+    #[must_use]
+    pub fn absorb(mut self, input: &[u8]) -> Self {
+        self.0.update(input);
+        self
+    }
+
+    /// Finish absorbing, and start producing XOF output
+    pub fn finalize(self) -> ShakeReader<Shake> {
+        ShakeReader(self.0.finalize_xof())
+    }
+
+    /// Finish absorbing, and return the first `N` bytes of XOF output
+    pub fn squeeze_new<N: ArraySize>(self) -> Array<u8, N> {
+        self.finalize().squeeze_new()
+    }
+}
+
+/// A finalized SHAKE computation that is producing output
+pub struct ShakeReader<Shake: ExtendableOutput>(Shake::Reader);
+
+impl<Shake: ExtendableOutput> ShakeReader<Shake> {
+    /// Fill `output` with the next bytes of XOF output (`H.Squeeze` / `G.Squeeze`)
+    pub fn squeeze(&mut self, output: &mut [u8]) -> &mut Self {
+        self.0.read(output);
+        self
+    }
+
+    /// Squeeze the next `N` bytes of XOF output into a new array
+    pub fn squeeze_new<N: ArraySize>(&mut self) -> Array<u8, N> {
+        let mut v = Array::default();
+        self.squeeze(&mut v);
+        v
+    }
+}
+
+/// SHAKE-128, denoted `G` in FIPS 204
+pub type G = ShakeState<Shake128>;
+/// SHAKE-256, denoted `H` in FIPS 204
+pub type H = ShakeState<Shake256>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hex_literal::hex;
+    use hybrid_array::typenum::U32;
+
+    #[test]
+    fn empty_input() {
+        let g: Array<u8, U32> = G::default().squeeze_new();
+        assert_eq!(
+            g.as_slice(),
+            hex!("7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26")
+        );
+
+        let h: Array<u8, U32> = H::default().squeeze_new();
+        assert_eq!(
+            h.as_slice(),
+            hex!("46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f")
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        // Absorbing in pieces is equivalent to absorbing all at once
+        let mut whole = H::default().absorb(b"hello world").finalize();
+        let mut parts = H::default()
+            .absorb(b"hello")
+            .absorb(b" ")
+            .absorb(b"world")
+            .finalize();
+
+        // Squeezing in pieces is equivalent to squeezing all at once
+        let mut expected = [0u8; 200];
+        whole.squeeze(&mut expected);
+
+        let mut actual = [0u8; 200];
+        let (a, b) = actual.split_at_mut(7);
+        parts.squeeze(a).squeeze(b);
+
+        assert_eq!(actual, expected);
+    }
+}
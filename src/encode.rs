@@ -1,7 +1,239 @@
 //! Encoding and decoding functions for ML-DSA
+//!
+//! `module_lattice::encode` handles unsigned coefficients (SimpleBitPack).  ML-DSA also packs
+//! signed coefficients drawn from a range `[-a, b]`, for `s1`, `s2`, `t0`, and `z`.  This module
+//! describes such ranges at the type level and implements BitPack / BitUnpack (FIPS 204
+//! Algorithms 17 and 19) on top of the unsigned encoding.
 
-/// Polynomial encoding utilities
-pub mod polynomial {
-    /// Polynomial encoding/decoding
-    pub struct PolynomialEncoder;
-}
\ No newline at end of file
+use core::ops::Add;
+use hybrid_array::{
+    Array,
+    typenum::{Len, Length, Sum, Unsigned},
+};
+
+use crate::algebra::{Elem, Polynomial, Vector};
+use crate::module_lattice::encode::{
+    ArraySize, Encode, EncodedPolynomialSize, EncodedVectorSize, EncodingSize, VectorEncodingSize,
+};
+
+/// A range `[-Min, Max]` of signed coefficients, described as the type-level pair `(Min, Max)`.
+/// Values in the range are encoded as `Max - x`, which lies in `[0, Min + Max]` and therefore
+/// needs `bitlen(Min + Max)` bits.
+pub trait RangeEncodingSize {
+    /// The magnitude of the lower end of the range
+    type Min: Unsigned;
+    /// The upper end of the range
+    type Max: Unsigned;
+    /// The number of bits used to encode each coefficient
+    type EncodingSize: EncodingSize;
+}
+
+impl<A, B> RangeEncodingSize for (A, B)
+where
+    A: Unsigned + Add<B>,
+    B: Unsigned,
+    Sum<A, B>: Len,
+    Length<Sum<A, B>>: EncodingSize,
+{
+    type Min = A;
+    type Max = B;
+    type EncodingSize = Length<Sum<A, B>>;
+}
+
+/// The lower end (in magnitude) of the range `[-A, B]`
+pub type RangeMin<A, B> = <(A, B) as RangeEncodingSize>::Min;
+/// The upper end of the range `[-A, B]`
+pub type RangeMax<A, B> = <(A, B) as RangeEncodingSize>::Max;
+/// The number of bits per coefficient for the range `[-A, B]`
+pub type RangeEncodingBits<A, B> = <(A, B) as RangeEncodingSize>::EncodingSize;
+/// The size of a polynomial packed with the range `[-A, B]`
+pub type RangeEncodedPolynomialSize<A, B> = EncodedPolynomialSize<RangeEncodingBits<A, B>>;
+/// A polynomial packed with the range `[-A, B]`
+pub type RangeEncodedPolynomial<A, B> = Array<u8, RangeEncodedPolynomialSize<A, B>>;
+/// The size of a vector of `K` polynomials packed with the range `[-A, B]`
+pub type RangeEncodedVectorSize<A, B, K> = EncodedVectorSize<RangeEncodingBits<A, B>, K>;
+/// A vector of `K` polynomials packed with the range `[-A, B]`
+pub type RangeEncodedVector<A, B, K> = Array<u8, RangeEncodedVectorSize<A, B, K>>;
+
+/// Packing of signed coefficients in the range `[-A, B]`
+pub trait BitPack<A, B>: Sized {
+    /// The size of the packed representation
+    type PackedSize: ArraySize;
+
+    /// Pack the coefficients (Algorithm 17 BitPack)
+    ///
+    /// Every coefficient must lie in `[-A, B]`; other values do not survive a round trip.
+    fn pack(&self) -> Array<u8, Self::PackedSize>;
+
+    /// Unpack the coefficients (Algorithm 19 BitUnpack)
+    ///
+    /// Returns `None` if any encoded coefficient lies outside `[-A, B]`.  This can only happen
+    /// when `A + B + 1` is not a power of two, e.g., for the `eta` ranges.
+    fn unpack(enc: &Array<u8, Self::PackedSize>) -> Option<Self>;
+}
+
+impl<A, B> BitPack<A, B> for Polynomial
+where
+    (A, B): RangeEncodingSize,
+{
+    type PackedSize = RangeEncodedPolynomialSize<A, B>;
+
+    fn pack(&self) -> RangeEncodedPolynomial<A, B> {
+        let a = Elem::new(RangeMin::<A, B>::U32);
+        let b = Elem::new(RangeMax::<A, B>::U32);
+
+        let to_encode = Self::new(
+            self.0
+                .iter()
+                .map(|w| {
+                    debug_assert!(w.0 <= b.0 || w.0 >= (-a).0);
+                    b - *w
+                })
+                .collect(),
+        );
+        Encode::<RangeEncodingBits<A, B>>::encode(&to_encode)
+    }
+
+    fn unpack(enc: &RangeEncodedPolynomial<A, B>) -> Option<Self> {
+        let a = RangeMin::<A, B>::U32;
+        let b = Elem::new(RangeMax::<A, B>::U32);
+
+        let x: Self = Encode::<RangeEncodingBits<A, B>>::decode(enc);
+        if x.0.iter().any(|x| x.0 > a + b.0) {
+            return None;
+        }
+
+        Some(Self::new(x.0.iter().map(|x| b - *x).collect()))
+    }
+}
+
+impl<K, A, B> BitPack<A, B> for Vector<K>
+where
+    K: ArraySize,
+    (A, B): RangeEncodingSize,
+    RangeEncodingBits<A, B>: VectorEncodingSize<K>,
+{
+    type PackedSize = RangeEncodedVectorSize<A, B, K>;
+
+    fn pack(&self) -> RangeEncodedVector<A, B, K> {
+        let polys = self.0.iter().map(BitPack::<A, B>::pack).collect();
+        RangeEncodingBits::<A, B>::flatten(polys)
+    }
+
+    fn unpack(enc: &RangeEncodedVector<A, B, K>) -> Option<Self> {
+        let unfold = RangeEncodingBits::<A, B>::unflatten(enc);
+        let polys = Array::try_from_fn(|i| BitPack::<A, B>::unpack(unfold[i]).ok_or(()));
+        polys.ok().map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::{AlgebraExt, BaseField, Field};
+    use hybrid_array::typenum::{Diff, Shleft, U1, U2, U3, U4, U12, U17, U19};
+
+    type Gamma1Small = Shleft<U1, U17>;
+    type Gamma1Large = Shleft<U1, U19>;
+    type TwoToDMinus1 = Shleft<U1, U12>;
+
+    // A polynomial that hits both ends of the range [-a, b] and a spread of values in between
+    fn range_poly(a: u32, b: u32) -> Polynomial {
+        let width = a + b + 1;
+        let mut p = Polynomial::new(Array::from_fn(|i| {
+            let i = u32::try_from(i).unwrap();
+            let x = (i * 7919) % width;
+            Elem::new(x) - Elem::new(a)
+        }));
+        p.0[0] = -Elem::new(a);
+        p.0[255] = Elem::new(b);
+        p
+    }
+
+    fn round_trip<A, B>()
+    where
+        A: Unsigned,
+        B: Unsigned,
+        (A, B): RangeEncodingSize,
+    {
+        let a = A::U32;
+        let b = B::U32;
+        let p = range_poly(a, b);
+
+        let enc = BitPack::<A, B>::pack(&p);
+        assert_eq!(BitPack::<A, B>::unpack(&enc), Some(p));
+    }
+
+    #[test]
+    fn polynomial_round_trip() {
+        // s1, s2 for eta = 2 and eta = 4
+        round_trip::<U2, U2>();
+        round_trip::<U4, U4>();
+
+        // t0
+        round_trip::<Diff<TwoToDMinus1, U1>, TwoToDMinus1>();
+
+        // z
+        round_trip::<Diff<Gamma1Small, U1>, Gamma1Small>();
+        round_trip::<Diff<Gamma1Large, U1>, Gamma1Large>();
+    }
+
+    #[test]
+    fn encoding_sizes() {
+        assert_eq!(RangeEncodedPolynomialSize::<U2, U2>::USIZE, 96);
+        assert_eq!(RangeEncodedPolynomialSize::<U4, U4>::USIZE, 128);
+        assert_eq!(
+            RangeEncodedPolynomialSize::<Diff<TwoToDMinus1, U1>, TwoToDMinus1>::USIZE,
+            32 * 13
+        );
+        assert_eq!(
+            RangeEncodedPolynomialSize::<Diff<Gamma1Small, U1>, Gamma1Small>::USIZE,
+            576
+        );
+        assert_eq!(
+            RangeEncodedPolynomialSize::<Diff<Gamma1Large, U1>, Gamma1Large>::USIZE,
+            640
+        );
+    }
+
+    #[test]
+    fn unpack_rejects_out_of_range() {
+        // With eta = 2, each coefficient uses 3 bits but only the values 0..=4 are valid
+        let mut enc = BitPack::<U2, U2>::pack(&Polynomial::default());
+        assert!(<Polynomial as BitPack<U2, U2>>::unpack(&enc).is_some());
+
+        // Encoded value 5 in the first coefficient
+        enc[0] = (enc[0] & !0x07) | 0x05;
+        assert!(<Polynomial as BitPack<U2, U2>>::unpack(&enc).is_none());
+
+        // With eta = 4, each coefficient uses 4 bits but only the values 0..=8 are valid
+        let mut enc = BitPack::<U4, U4>::pack(&Polynomial::default());
+        enc[127] = 0x90;
+        assert!(<Polynomial as BitPack<U4, U4>>::unpack(&enc).is_none());
+    }
+
+    #[test]
+    fn vector_round_trip() {
+        let v = Vector::<U3>::new(Array([
+            range_poly(2, 2),
+            -&range_poly(2, 2),
+            Polynomial::default(),
+        ]));
+        let enc = BitPack::<U2, U2>::pack(&v);
+        assert_eq!(enc.len(), 3 * 96);
+
+        let dec: Vector<U3> = BitPack::<U2, U2>::unpack(&enc).unwrap();
+        assert_eq!(dec, v);
+        assert!(dec.infinity_norm() <= 2);
+
+        // An out-of-range value in any polynomial is reported
+        let mut bad = enc;
+        bad[2 * 96] = 0x07;
+        assert!(<Vector<U3> as BitPack<U2, U2>>::unpack(&bad).is_none());
+
+        // Unpacked coefficients are proper field elements
+        let z = BitPack::<U4, U4>::pack(&Vector::<U3>::default());
+        let z: Vector<U3> = BitPack::<U4, U4>::unpack(&z).unwrap();
+        assert!(z.0.iter().all(|p| p.0.iter().all(|x| x.0 < BaseField::Q)));
+    }
+}
@@ -0,0 +1,273 @@
+//! The reasons an operation can fail
+//!
+//! Public functions return the opaque [`signature::Error`], as the `signature` traits require.
+//! With the `alloc` feature, that error carries an [`MlDsaError`] as its source, which tells a
+//! truncated signature from one that does not verify, or a bad context string from a failed
+//! random number generator.  None of the reasons depend on secret values.
+
+use core::fmt;
+
+use crate::inspect::SignatureViolation;
+use crate::seed::SeedUnavailable;
+use crate::validate::KeyValidationError;
+
+/// The reason an ML-DSA operation failed
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MlDsaError {
+    /// An encoded key, signature, seed, or digest does not have the expected length
+    InvalidLength,
+    /// The context string is longer than 255 bytes
+    ContextTooLong,
+    /// The hint in a signature is not canonically encoded
+    NonCanonicalHint,
+    /// A coefficient of a key or signature lies outside its allowed range
+    CoefficientOutOfRange,
+    /// Two keys, or the parts of one key, do not belong together
+    KeyMismatch,
+    /// The algorithm identifier or encoded length does not name a supported algorithm
+    UnsupportedAlgorithm,
+    /// The signature does not verify
+    InvalidSignature,
+    /// The seed of an expanded signing key was requested
+    SeedUnavailable,
+    /// The random number generator failed
+    RngFailure,
+    /// A self-test failed, and the module is in the error state
+    SelfTestFailure,
+    /// A freshly produced signature did not verify, which points to a fault during signing
+    FaultDetected,
+}
+
+impl MlDsaError {
+    /// The reason attached to an error returned by this crate, if any
+    #[cfg(feature = "alloc")]
+    pub fn from_signature_error(err: &signature::Error) -> Option<Self> {
+        let source = std::error::Error::source(err)?;
+        source.downcast_ref::<Self>().copied()
+    }
+}
+
+impl fmt::Display for MlDsaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidLength => "invalid encoding length",
+            Self::ContextTooLong => "context string longer than 255 bytes",
+            Self::NonCanonicalHint => "non-canonical hint encoding",
+            Self::CoefficientOutOfRange => "coefficient out of range",
+            Self::KeyMismatch => "keys do not match",
+            Self::UnsupportedAlgorithm => "unsupported algorithm",
+            Self::InvalidSignature => "invalid signature",
+            Self::SeedUnavailable => "seed unavailable for an expanded signing key",
+            Self::RngFailure => "random number generator failure",
+            Self::SelfTestFailure => "self-test failure",
+            Self::FaultDetected => "signature failed verification after signing",
+        })
+    }
+}
+
+impl std::error::Error for MlDsaError {}
+
+impl From<MlDsaError> for signature::Error {
+    #[cfg(feature = "alloc")]
+    fn from(err: MlDsaError) -> Self {
+        Self::from_source(err)
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn from(_: MlDsaError) -> Self {
+        Self::new()
+    }
+}
+
+impl From<KeyValidationError> for MlDsaError {
+    fn from(err: KeyValidationError) -> Self {
+        match err {
+            KeyValidationError::InvalidLength => Self::InvalidLength,
+            KeyValidationError::S1OutOfRange | KeyValidationError::S2OutOfRange => {
+                Self::CoefficientOutOfRange
+            }
+            KeyValidationError::T0Mismatch
+            | KeyValidationError::TrMismatch
+            | KeyValidationError::VerifyingKeyMismatch => Self::KeyMismatch,
+        }
+    }
+}
+
+impl From<SignatureViolation> for MlDsaError {
+    fn from(err: SignatureViolation) -> Self {
+        match err {
+            SignatureViolation::ZOutOfRange { .. } => Self::CoefficientOutOfRange,
+            SignatureViolation::HintCountDecreasing { .. }
+            | SignatureViolation::HintCountExceedsOmega { .. }
+            | SignatureViolation::HintIndexOrder { .. }
+            | SignatureViolation::HintPadding { .. } => Self::NonCanonicalHint,
+        }
+    }
+}
+
+impl From<SeedUnavailable> for MlDsaError {
+    fn from(_: SeedUnavailable) -> Self {
+        Self::SeedUnavailable
+    }
+}
+
+impl From<KeyValidationError> for signature::Error {
+    fn from(err: KeyValidationError) -> Self {
+        MlDsaError::from(err).into()
+    }
+}
+
+impl From<SignatureViolation> for signature::Error {
+    fn from(err: SignatureViolation) -> Self {
+        MlDsaError::from(err).into()
+    }
+}
+
+impl From<SeedUnavailable> for signature::Error {
+    fn from(err: SeedUnavailable) -> Self {
+        MlDsaError::from(err).into()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+    use crate::util::B32;
+    use crate::{
+        AnySignature, AnyVerifyingKey, KeyGen, MlDsa44, MlDsa65, SeedSigningKey, Signature,
+        SigningKey,
+    };
+    use signature::{Signer, Verifier};
+
+    fn reason<T>(result: Result<T, signature::Error>) -> Option<MlDsaError> {
+        MlDsaError::from_signature_error(&result.err().unwrap())
+    }
+
+    #[test]
+    fn reasons() {
+        let kp = MlDsa44::key_gen_internal(&B32::from([1; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+        let other = MlDsa44::key_gen_internal(&B32::from([2; 32])).unwrap();
+        let sig = sk.sign(b"message");
+        let enc = sig.encode();
+
+        // A truncated signature and one made with a different key fail differently
+        assert_eq!(
+            reason(Signature::<MlDsa44>::try_from(&enc[1..])),
+            Some(MlDsaError::InvalidLength)
+        );
+        assert_eq!(
+            reason(other.verifying_key().verify(b"message", &sig)),
+            Some(MlDsaError::InvalidSignature)
+        );
+
+        // The last byte counts the hints of the last polynomial, which may not exceed omega
+        let mut bad = enc;
+        bad[enc.len() - 1] = 0xff;
+        assert_eq!(
+            reason(Signature::<MlDsa44>::try_from(bad.as_slice())),
+            Some(MlDsaError::NonCanonicalHint)
+        );
+
+        let too_long = [0; 256];
+        assert_eq!(
+            reason(sk.sign_deterministic(b"message", &too_long)),
+            Some(MlDsaError::ContextTooLong)
+        );
+        assert_eq!(
+            reason(vk.verify_with_context(b"message", &too_long, &sig)),
+            Some(MlDsaError::ContextTooLong)
+        );
+
+        // eta = 2: an s1 coefficient encoded as 7 is out of range
+        let mut bad = sk.encode();
+        bad[128] |= 0x07;
+        assert_eq!(
+            reason(SigningKey::<MlDsa44>::try_from(bad.as_slice())),
+            Some(MlDsaError::CoefficientOutOfRange)
+        );
+
+        // A signature for a different parameter set than the key
+        let vk65 = AnyVerifyingKey::from(
+            MlDsa65::key_gen_internal(&B32::default())
+                .unwrap()
+                .verifying_key()
+                .clone(),
+        );
+        assert_eq!(
+            reason(vk65.verify_with_context(b"message", &[], &AnySignature::from(sig))),
+            Some(MlDsaError::KeyMismatch)
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(
+            MlDsaError::from(KeyValidationError::TrMismatch),
+            MlDsaError::KeyMismatch
+        );
+        assert_eq!(
+            MlDsaError::from(KeyValidationError::S2OutOfRange),
+            MlDsaError::CoefficientOutOfRange
+        );
+        assert_eq!(
+            MlDsaError::from(SignatureViolation::HintPadding { offset: 3 }),
+            MlDsaError::NonCanonicalHint
+        );
+        assert_eq!(
+            MlDsaError::from_signature_error(&SeedUnavailable.into()),
+            Some(MlDsaError::SeedUnavailable)
+        );
+
+        // Errors without a reason attached
+        assert_eq!(
+            MlDsaError::from_signature_error(&signature::Error::new()),
+            None
+        );
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn rng_failure() {
+        use rand_core::{TryCryptoRng, TryRngCore};
+        use signature::RandomizedSigner;
+
+        struct FailingRng;
+
+        impl TryRngCore for FailingRng {
+            type Error = MlDsaError;
+
+            fn try_next_u32(&mut self) -> Result<u32, MlDsaError> {
+                Err(MlDsaError::RngFailure)
+            }
+
+            fn try_next_u64(&mut self) -> Result<u64, MlDsaError> {
+                Err(MlDsaError::RngFailure)
+            }
+
+            fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), MlDsaError> {
+                Err(MlDsaError::RngFailure)
+            }
+        }
+
+        impl TryCryptoRng for FailingRng {}
+
+        let kp = MlDsa44::key_gen_internal(&B32::from([1; 32])).unwrap();
+        assert_eq!(
+            reason(
+                kp.signing_key()
+                    .try_sign_with_rng(&mut FailingRng, b"message")
+            ),
+            Some(MlDsaError::RngFailure)
+        );
+        assert_eq!(
+            reason(MlDsa44::key_gen(&mut FailingRng)),
+            Some(MlDsaError::RngFailure)
+        );
+        assert_eq!(
+            reason(SeedSigningKey::<MlDsa44>::generate(&mut FailingRng)),
+            Some(MlDsaError::RngFailure)
+        );
+    }
+}
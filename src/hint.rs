@@ -1,4 +1,284 @@
 //! Hint generation and verification for ML-DSA
+//!
+//! The signer cannot reveal the low-order part of `w`, so it sends a hint vector `h` that lets
+//! the verifier recover `w1` from `A*z - c*t1*2^d` (FIPS 204 Section 7.4, Algorithms 39 and 40).
+//! Hints are serialized as a list of set-bit positions (Algorithms 20 and 21).  Decoding rejects
+//! every non-canonical encoding, which is required for strong unforgeability.
 
-/// Hint operations
-pub struct Hint;
\ No newline at end of file
+use core::ops::Add;
+use hybrid_array::{
+    Array, ArraySize,
+    typenum::{Sum, U256, Unsigned},
+};
+
+use crate::algebra::{AlgebraExt, BaseField, Decompose, Elem, Field, Polynomial, Truncate, Vector};
+
+/// The encoded form of a hint with `K` polynomials and at most `Omega` set bits
+pub type EncodedHint<K, Omega> = Array<u8, Sum<Omega, K>>;
+
+// Algorithm 39 MakeHint
+fn make_hint<TwoGamma2: Unsigned>(z: Elem, r: Elem) -> bool {
+    let r1 = r.high_bits::<TwoGamma2>();
+    let v1 = (r + z).high_bits::<TwoGamma2>();
+    r1 != v1
+}
+
+// Algorithm 40 UseHint
+#[allow(clippy::integer_division_remainder_used)]
+fn use_hint<TwoGamma2: Unsigned>(h: bool, r: Elem) -> Elem {
+    let m: u32 = (BaseField::Q - 1) / TwoGamma2::U32;
+    let (r1, r0) = r.decompose::<TwoGamma2>();
+
+    if !h {
+        return r1;
+    }
+
+    // r0 is stored as a field element, so positive values are those in (0, (q-1)/2]
+    let r0_positive = r0.0 > 0 && r0.0 <= BaseField::Q >> 1;
+    if r0_positive {
+        Elem::new((r1.0 + 1) % m)
+    } else {
+        Elem::new((r1.0 + m - 1) % m)
+    }
+}
+
+/// A hint: `K` polynomials with boolean coefficients
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hint<K: ArraySize>(pub Array<Array<bool, U256>, K>);
+
+impl<K: ArraySize> Default for Hint<K> {
+    fn default() -> Self {
+        Self(Array::from_fn(|_| Array::from_fn(|_| false)))
+    }
+}
+
+impl<K: ArraySize> Hint<K> {
+    /// Compute the hint for adding `z` to `r` (Algorithm 39 MakeHint, applied coefficient-wise)
+    pub fn new<TwoGamma2: Unsigned>(z: &Vector<K>, r: &Vector<K>) -> Self {
+        let zi = z.0.iter();
+        let ri = r.0.iter();
+
+        Self(
+            zi.zip(ri)
+                .map(|(zv, rv)| {
+                    let zvi = zv.0.iter();
+                    let rvi = rv.0.iter();
+
+                    zvi.zip(rvi)
+                        .map(|(&z, &r)| make_hint::<TwoGamma2>(z, r))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// The number of set bits in the hint
+    pub fn hamming_weight(&self) -> usize {
+        self.0
+            .iter()
+            .map(|x| x.iter().filter(|x| **x).count())
+            .sum()
+    }
+
+    /// Recover the high bits of `r + z` from `r` (Algorithm 40 UseHint, applied
+    /// coefficient-wise)
+    pub fn use_hint<TwoGamma2: Unsigned>(&self, r: &Vector<K>) -> Vector<K> {
+        let hi = self.0.iter();
+        let ri = r.0.iter();
+
+        Vector::new(
+            hi.zip(ri)
+                .map(|(hv, rv)| {
+                    let hvi = hv.iter();
+                    let rvi = rv.0.iter();
+
+                    Polynomial::new(
+                        hvi.zip(rvi)
+                            .map(|(&h, &r)| use_hint::<TwoGamma2>(h, r))
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Encode the hint as the positions of its set bits (Algorithm 20 HintBitPack)
+    ///
+    /// The caller is responsible for ensuring that at most `Omega` bits are set.
+    pub fn bit_pack<Omega>(&self) -> EncodedHint<K, Omega>
+    where
+        Omega: Unsigned + Add<K>,
+        Sum<Omega, K>: ArraySize,
+    {
+        let mut y: EncodedHint<K, Omega> = Array::default();
+        let mut index = 0;
+        let omega = Omega::USIZE;
+
+        for (i, h) in self.0.iter().enumerate() {
+            for (j, _) in h.iter().enumerate().filter(|(_, h)| **h) {
+                y[index] = Truncate::truncate(j);
+                index += 1;
+            }
+
+            y[omega + i] = Truncate::truncate(index);
+        }
+
+        y
+    }
+
+    /// Decode an encoded hint (Algorithm 21 HintBitUnpack)
+    ///
+    /// Returns `None` for any encoding that is not the canonical encoding of some hint with at
+    /// most `Omega` set bits: cumulative counts that decrease or exceed `Omega`, positions that
+    /// are not strictly increasing within a polynomial, or nonzero padding.
+    pub fn bit_unpack<Omega>(y: &EncodedHint<K, Omega>) -> Option<Self>
+    where
+        Omega: Unsigned + Add<K>,
+        Sum<Omega, K>: ArraySize,
+    {
+        let (indices, cuts) = y.split_at(Omega::USIZE);
+        let cuts: Array<usize, K> = cuts.iter().map(|x| usize::from(*x)).collect();
+
+        let mut h = Self::default();
+        let mut start = 0;
+        for (i, &end) in cuts.iter().enumerate() {
+            if end < start || end > Omega::USIZE {
+                return None;
+            }
+
+            let indices = &indices[start..end];
+            if indices.windows(2).any(|w| w[0] >= w[1]) {
+                return None;
+            }
+
+            for &j in indices {
+                h.0[i][usize::from(j)] = true;
+            }
+
+            start = end;
+        }
+
+        if indices[start..].iter().any(|x| *x != 0) {
+            return None;
+        }
+
+        Some(h)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::QMinus1;
+    use hybrid_array::typenum::{Quot, U2, U4, U16, U44};
+
+    type TwoGamma2Small = Quot<QMinus1, U44>;
+    type TwoGamma2Large = Quot<QMinus1, U16>;
+
+    // Deterministic pseudorandom vector with coefficients in [-bound, bound]
+    fn small_vector<K: ArraySize>(seed: u64, bound: u32) -> Vector<K> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            u32::try_from(state >> 33).unwrap()
+        };
+
+        Vector::new(Array::from_fn(|_| {
+            Polynomial::new(Array::from_fn(|_| {
+                let x = next() % (2 * bound + 1);
+                Elem::new(x) - Elem::new(bound)
+            }))
+        }))
+    }
+
+    fn uniform_vector<K: ArraySize>(seed: u64) -> Vector<K> {
+        small_vector(seed, BaseField::Q / 2)
+    }
+
+    fn check_use_hint<TwoGamma2: Unsigned>() {
+        let gamma2 = TwoGamma2::U32 / 2;
+
+        for seed in 0..8 {
+            let r = uniform_vector::<U4>(seed);
+            let z = small_vector::<U4>(seed + 100, gamma2);
+
+            let h = Hint::new::<TwoGamma2>(&z, &r);
+            let expected = (&r + &z).high_bits::<TwoGamma2>();
+            assert_eq!(h.use_hint::<TwoGamma2>(&r), expected);
+
+            // Without a hint, UseHint is just HighBits
+            let h = Hint::<U4>::default();
+            assert_eq!(h.use_hint::<TwoGamma2>(&r), r.high_bits::<TwoGamma2>());
+        }
+    }
+
+    #[test]
+    fn make_and_use_hint() {
+        check_use_hint::<TwoGamma2Small>();
+        check_use_hint::<TwoGamma2Large>();
+    }
+
+    #[test]
+    fn bit_pack_round_trip() {
+        let mut h = Hint::<U4>::default();
+        h.0[0][3] = true;
+        h.0[0][200] = true;
+        h.0[2][0] = true;
+        h.0[2][255] = true;
+        h.0[3][17] = true;
+        assert_eq!(h.hamming_weight(), 5);
+
+        let y = h.bit_pack::<U16>();
+        assert_eq!(
+            y.as_slice(),
+            [
+                3, 200, 0, 255, 17, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 2, 4, 5
+            ]
+        );
+        assert_eq!(Hint::bit_unpack::<U16>(&y), Some(h));
+
+        // The empty hint and a full hint both round-trip
+        let h = Hint::<U2>::default();
+        assert_eq!(Hint::bit_unpack::<U4>(&h.bit_pack::<U4>()), Some(h));
+
+        let mut h = Hint::<U2>::default();
+        h.0[0][1] = true;
+        h.0[0][2] = true;
+        h.0[1][1] = true;
+        h.0[1][2] = true;
+        assert_eq!(Hint::bit_unpack::<U4>(&h.bit_pack::<U4>()), Some(h));
+    }
+
+    #[test]
+    fn bit_unpack_rejects_malformed() {
+        type Encoded = EncodedHint<U2, U4>;
+
+        // Well-formed baseline
+        let y = Encoded::from([1, 5, 2, 0, 2, 3]);
+        assert!(Hint::<U2>::bit_unpack::<U4>(&y).is_some());
+
+        // Indices within a polynomial must be strictly increasing
+        let y = Encoded::from([5, 1, 2, 0, 2, 3]);
+        assert!(Hint::<U2>::bit_unpack::<U4>(&y).is_none());
+        let y = Encoded::from([5, 5, 2, 0, 2, 3]);
+        assert!(Hint::<U2>::bit_unpack::<U4>(&y).is_none());
+
+        // ... but may restart in the next polynomial
+        let y = Encoded::from([5, 6, 1, 0, 2, 3]);
+        assert!(Hint::<U2>::bit_unpack::<U4>(&y).is_some());
+
+        // Cumulative counts may not decrease
+        let y = Encoded::from([1, 5, 2, 0, 2, 1]);
+        assert!(Hint::<U2>::bit_unpack::<U4>(&y).is_none());
+
+        // Cumulative counts may not exceed omega
+        let y = Encoded::from([1, 2, 3, 4, 2, 5]);
+        assert!(Hint::<U2>::bit_unpack::<U4>(&y).is_none());
+
+        // Padding after the last index must be zero
+        let y = Encoded::from([1, 5, 2, 7, 2, 3]);
+        assert!(Hint::<U2>::bit_unpack::<U4>(&y).is_none());
+    }
+}
@@ -0,0 +1,407 @@
+//! Structured decoding of signatures, for audits and diagnostics
+//!
+//! ML-DSA is strongly unforgeable only because every signature has exactly one valid encoding:
+//! sigDecode (Algorithm 27) rejects hints whose positions are out of order, whose counts exceed
+//! `omega`, or whose padding is nonzero, and verification rejects `z` with
+//! `||z||∞ >= gamma1 - beta`.  [`Signature::decode_strict`] applies all of those checks and
+//! reports the first violation.  [`Signature::inspect`] instead decodes as much as it can and
+//! reports every violation it finds, so that a malleated copy of a valid signature can be told
+//! apart from a forgery: the canonical re-encoding of the former still verifies.
+
+use core::fmt;
+use hybrid_array::typenum::Unsigned;
+
+use crate::algebra::{AlgebraExt, Vector};
+use crate::hint::Hint;
+use crate::param::{EncodedCTilde, EncodedSignature, EncodedSignatureHint};
+use crate::{MlDsaParams, Signature};
+
+/// A way in which an encoded signature fails to be canonical
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SignatureViolation {
+    /// A coefficient of `z` is at least `gamma1 - beta` in absolute value
+    ZOutOfRange {
+        /// The polynomial of `z` that holds the coefficient
+        poly: usize,
+        /// The index of the coefficient within the polynomial
+        coeff: usize,
+    },
+    /// The cumulative hint count of a polynomial is smaller than that of the previous one
+    HintCountDecreasing {
+        /// The polynomial of the hint
+        poly: usize,
+    },
+    /// The cumulative hint count of a polynomial exceeds `omega`
+    HintCountExceedsOmega {
+        /// The polynomial of the hint
+        poly: usize,
+    },
+    /// A hint position is not greater than the one before it in the same polynomial
+    HintIndexOrder {
+        /// The polynomial of the hint
+        poly: usize,
+        /// The offset of the position in the encoded hint
+        offset: usize,
+    },
+    /// A hint position after the last one in use is nonzero
+    HintPadding {
+        /// The offset of the position in the encoded hint
+        offset: usize,
+    },
+}
+
+impl fmt::Display for SignatureViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZOutOfRange { poly, coeff } => {
+                write!(f, "z[{poly}][{coeff}] is out of range")
+            }
+            Self::HintCountDecreasing { poly } => {
+                write!(f, "hint count of polynomial {poly} decreases")
+            }
+            Self::HintCountExceedsOmega { poly } => {
+                write!(f, "hint count of polynomial {poly} exceeds omega")
+            }
+            Self::HintIndexOrder { poly, offset } => {
+                write!(
+                    f,
+                    "hint position at offset {offset} in polynomial {poly} is out of order"
+                )
+            }
+            Self::HintPadding { offset } => {
+                write!(f, "hint padding at offset {offset} is nonzero")
+            }
+        }
+    }
+}
+
+/// The result of decoding a signature leniently
+///
+/// Created by [`Signature::inspect`].  The components are decoded as far as possible: hint
+/// positions are taken as a set, whatever their order, and positions beyond `omega` or in the
+/// padding are ignored.
+#[derive(Clone, Debug)]
+pub struct SignatureReport<P: MlDsaParams> {
+    c_tilde: EncodedCTilde<P>,
+    z: Vector<P::L>,
+    h: Hint<P::K>,
+    violations: Vec<SignatureViolation>,
+}
+
+impl<P: MlDsaParams> SignatureReport<P> {
+    /// The commitment hash `c̃`
+    pub fn c_tilde(&self) -> &EncodedCTilde<P> {
+        &self.c_tilde
+    }
+
+    /// The response vector `z`
+    pub fn z(&self) -> &Vector<P::L> {
+        &self.z
+    }
+
+    /// The hint, as far as it could be decoded
+    pub fn hint(&self) -> &Hint<P::K> {
+        &self.h
+    }
+
+    /// Every violation found, in the order of the encoding
+    pub fn violations(&self) -> &[SignatureViolation] {
+        &self.violations
+    }
+
+    /// Whether the encoding is the canonical encoding of a well-formed signature
+    pub fn is_canonical(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// The well-formed signature with the decoded components, if there is one
+    ///
+    /// Returns `None` if `z` is out of range or the hint has more than `omega` set bits.
+    /// Otherwise the result has a canonical encoding, which may differ from the inspected one;
+    /// if it verifies, the inspected bytes are a malleated copy of a valid signature.
+    pub fn canonical(&self) -> Option<Signature<P>> {
+        let z_ok = self.z.infinity_norm() < P::GAMMA1_MINUS_BETA;
+        let h_ok = self.h.hamming_weight() <= P::Omega::USIZE;
+
+        (z_ok && h_ok).then(|| Signature {
+            c_tilde: self.c_tilde.clone(),
+            z: self.z.clone(),
+            h: self.h.clone(),
+        })
+    }
+}
+
+// Check the bound on z, coefficient by coefficient
+fn z_violations<P: MlDsaParams>(z: &Vector<P::L>, violations: &mut Vec<SignatureViolation>) {
+    for (poly, p) in z.0.iter().enumerate() {
+        for (coeff, x) in p.0.iter().enumerate() {
+            if x.infinity_norm() >= P::GAMMA1_MINUS_BETA {
+                violations.push(SignatureViolation::ZOutOfRange { poly, coeff });
+            }
+        }
+    }
+}
+
+// Algorithm 21 HintBitUnpack, carrying on past malformed input
+fn hint_lenient<P: MlDsaParams>(
+    y: &EncodedSignatureHint<P>,
+    violations: &mut Vec<SignatureViolation>,
+) -> Hint<P::K> {
+    let omega = P::Omega::USIZE;
+    let (indices, cuts) = y.split_at(omega);
+
+    let mut h = Hint::default();
+    let mut start = 0;
+    for (poly, &end) in cuts.iter().enumerate() {
+        let mut end = usize::from(end);
+        if end < start {
+            violations.push(SignatureViolation::HintCountDecreasing { poly });
+            end = start;
+        }
+        if end > omega {
+            violations.push(SignatureViolation::HintCountExceedsOmega { poly });
+            end = omega;
+        }
+
+        for offset in start..end {
+            if offset > start && indices[offset] <= indices[offset - 1] {
+                violations.push(SignatureViolation::HintIndexOrder { poly, offset });
+            }
+            h.0[poly][usize::from(indices[offset])] = true;
+        }
+
+        start = end;
+    }
+
+    for (offset, &x) in indices.iter().enumerate().skip(start) {
+        if x != 0 {
+            violations.push(SignatureViolation::HintPadding { offset });
+        }
+    }
+
+    h
+}
+
+impl<P: MlDsaParams> Signature<P> {
+    /// The commitment hash `c̃`
+    pub fn c_tilde(&self) -> &EncodedCTilde<P> {
+        &self.c_tilde
+    }
+
+    /// The response vector `z`
+    pub fn z(&self) -> &Vector<P::L> {
+        &self.z
+    }
+
+    /// The hint `h`
+    pub fn hint(&self) -> &Hint<P::K> {
+        &self.h
+    }
+
+    /// Decode a signature, checking every canonicity condition (Algorithm 27 sigDecode, plus
+    /// the bound on `z` from Algorithm 8 line 13)
+    ///
+    /// Unlike [`Signature::decode`], this rejects a `z` that verification would reject, and
+    /// says why the encoding was rejected.  Returns the first violation found.
+    pub fn decode_strict(enc: &EncodedSignature<P>) -> Result<Self, SignatureViolation> {
+        let report = Self::inspect(enc);
+        match report.violations.first() {
+            Some(v) => Err(*v),
+            None => Ok(Self {
+                c_tilde: report.c_tilde,
+                z: report.z,
+                h: report.h,
+            }),
+        }
+    }
+
+    /// Decode a signature leniently, collecting every canonicity violation instead of stopping
+    /// at the first one
+    ///
+    /// This is meant for diagnostics only; use [`Signature::decode_strict`] or
+    /// [`Signature::decode`] to decode signatures that are to be verified.
+    pub fn inspect(enc: &EncodedSignature<P>) -> SignatureReport<P> {
+        let (c_tilde, z, h) = P::split_sig(enc);
+        let mut violations = Vec::new();
+
+        // The packed range [-(gamma1 - 1), gamma1] covers every bit pattern
+        let z = P::decode_z(z).unwrap_or_default();
+        z_violations::<P>(&z, &mut violations);
+        let h = hint_lenient::<P>(h, &mut violations);
+
+        SignatureReport {
+            c_tilde: c_tilde.clone(),
+            z,
+            h,
+            violations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::Elem;
+    use crate::param::SignatureParams;
+    use crate::util::B32;
+    use crate::{KeyGen, MlDsa44, MlDsa65, MlDsa87, VerifyingKey};
+
+    // A signature with at least two hint bits in some polynomial, with its key and message
+    fn signature<P: MlDsaParams>() -> (VerifyingKey<P>, Vec<u8>, Signature<P>) {
+        let (sk, vk) = P::key_gen_internal(&B32::from([8; 32]))
+            .unwrap()
+            .into_parts();
+        for i in 0..=u8::MAX {
+            let msg = vec![i; 16];
+            let sig = sk.sign_deterministic(&msg, &[]).unwrap();
+            if sig
+                .h
+                .0
+                .iter()
+                .any(|p| p.iter().filter(|x| **x).count() >= 2)
+            {
+                return (vk, msg, sig);
+            }
+        }
+        unreachable!()
+    }
+
+    // The offsets of the hint positions and of the hint counts in an encoded signature
+    fn hint_offsets<P: MlDsaParams>() -> (usize, usize) {
+        let cuts = P::SignatureSize::USIZE - P::K::USIZE;
+        (cuts - P::Omega::USIZE, cuts)
+    }
+
+    fn canonical_signature<P: MlDsaParams>() {
+        let (_, _, sig) = signature::<P>();
+        let enc = sig.encode();
+
+        let report = Signature::<P>::inspect(&enc);
+        assert!(report.is_canonical());
+        assert_eq!(report.c_tilde(), sig.c_tilde());
+        assert_eq!(report.z(), sig.z());
+        assert_eq!(report.hint(), sig.hint());
+        assert_eq!(report.canonical(), Some(sig.clone()));
+        assert_eq!(Signature::<P>::decode_strict(&enc), Ok(sig));
+    }
+
+    #[test]
+    fn canonical() {
+        canonical_signature::<MlDsa44>();
+        canonical_signature::<MlDsa65>();
+        canonical_signature::<MlDsa87>();
+    }
+
+    #[test]
+    fn malleated_or_forged() {
+        let (vk, msg, sig) = signature::<MlDsa65>();
+        let (positions, _) = hint_offsets::<MlDsa65>();
+        let weight = sig.h.hamming_weight();
+
+        // Nonzero padding after the last position in use
+        let mut enc = sig.encode();
+        enc[positions + weight] = 0x55;
+        assert_eq!(Signature::<MlDsa65>::decode(&enc), None);
+        let v = SignatureViolation::HintPadding { offset: weight };
+        assert_eq!(Signature::<MlDsa65>::decode_strict(&enc), Err(v));
+
+        // The contents are those of a valid signature, so this is malleation
+        let report = Signature::<MlDsa65>::inspect(&enc);
+        assert_eq!(report.violations(), [v]);
+        let canonical = report.canonical().unwrap();
+        assert_eq!(canonical, sig);
+        assert!(vk.verify_with_context(&msg, &[], &canonical).is_ok());
+
+        // With a different c~ as well, the canonical form does not verify: a forgery
+        enc[0] ^= 1;
+        let report = Signature::<MlDsa65>::inspect(&enc);
+        assert_eq!(report.violations(), [v]);
+        let canonical = report.canonical().unwrap();
+        assert!(vk.verify_with_context(&msg, &[], &canonical).is_err());
+    }
+
+    #[test]
+    fn every_violation_is_reported() {
+        let (_, _, sig) = signature::<MlDsa44>();
+        let (positions, cuts) = hint_offsets::<MlDsa44>();
+        let omega = <MlDsa44 as crate::ParameterSet>::Omega::USIZE;
+        assert!(sig.h.hamming_weight() < omega);
+
+        let mut bad = sig.clone();
+        bad.z.0[1].0[7] = Elem::new(MlDsa44::GAMMA1_MINUS_BETA);
+        bad.z.0[3].0[0] = -Elem::new(MlDsa44::GAMMA1_MINUS_BETA + 5);
+        let mut enc = bad.encode();
+
+        // Swap the first two positions of a polynomial with two or more hint bits
+        let counts: Vec<usize> = enc[cuts..].iter().map(|&x| usize::from(x)).collect();
+        let (poly, start) = (0..counts.len())
+            .map(|i| (i, if i == 0 { 0 } else { counts[i - 1] }))
+            .find(|&(i, start)| counts[i] - start >= 2)
+            .unwrap();
+        enc.swap(positions + start, positions + start + 1);
+
+        // Nonzero padding in the last position
+        enc[positions + omega - 1] = 1;
+
+        let report = Signature::<MlDsa44>::inspect(&enc);
+        assert_eq!(
+            report.violations(),
+            [
+                SignatureViolation::ZOutOfRange { poly: 1, coeff: 7 },
+                SignatureViolation::ZOutOfRange { poly: 3, coeff: 0 },
+                SignatureViolation::HintIndexOrder {
+                    poly,
+                    offset: start + 1
+                },
+                SignatureViolation::HintPadding { offset: omega - 1 },
+            ]
+        );
+        assert_eq!(report.canonical(), None);
+        assert_eq!(report.hint(), sig.hint());
+        assert_eq!(
+            Signature::<MlDsa44>::decode_strict(&enc),
+            Err(SignatureViolation::ZOutOfRange { poly: 1, coeff: 7 })
+        );
+    }
+
+    #[test]
+    fn malformed_counts() {
+        let (_, _, sig) = signature::<MlDsa87>();
+        let (_, cuts) = hint_offsets::<MlDsa87>();
+        let omega = <MlDsa87 as crate::ParameterSet>::Omega::USIZE;
+        let k = <MlDsa87 as crate::ParameterSet>::K::USIZE;
+
+        // A count beyond omega
+        let mut enc = sig.encode();
+        enc[cuts + k - 1] = u8::try_from(omega + 1).unwrap();
+        let report = Signature::<MlDsa87>::inspect(&enc);
+        let v = SignatureViolation::HintCountExceedsOmega { poly: k - 1 };
+        assert!(report.violations().contains(&v));
+        assert!(Signature::<MlDsa87>::decode_strict(&enc).is_err());
+
+        // A decreasing count
+        let mut enc = sig.encode();
+        enc[cuts + k - 2] = enc[cuts + k - 1] + 1;
+        enc[cuts + k - 1] -= 1;
+        let report = Signature::<MlDsa87>::inspect(&enc);
+        let v = SignatureViolation::HintCountDecreasing { poly: k - 1 };
+        assert!(report.violations().contains(&v));
+        assert!(Signature::<MlDsa87>::decode_strict(&enc).is_err());
+    }
+
+    #[test]
+    fn strict_rejects_what_verification_rejects() {
+        let (vk, msg, sig) = signature::<MlDsa44>();
+        let mut bad = sig;
+        bad.z.0[0].0[0] = Elem::new(MlDsa44::GAMMA1_MINUS_BETA);
+
+        // sigDecode accepts this, but verification and strict decoding reject it
+        let enc = bad.encode();
+        assert!(Signature::<MlDsa44>::decode(&enc).is_some());
+        assert!(vk.verify_with_context(&msg, &[], &bad).is_err());
+        assert_eq!(
+            Signature::<MlDsa44>::decode_strict(&enc),
+            Err(SignatureViolation::ZOutOfRange { poly: 0, coeff: 0 })
+        );
+    }
+}
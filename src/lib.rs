@@ -11,64 +11,1098 @@ pub mod module_lattice;
 #[forbid(unsafe_code)]
 pub mod algebra;
 #[forbid(unsafe_code)]
+pub mod any;
+#[forbid(unsafe_code)]
+pub mod batch;
+#[forbid(unsafe_code)]
 pub mod crypto;
 #[forbid(unsafe_code)]
 pub mod encode;
 #[forbid(unsafe_code)]
+pub mod error;
+#[forbid(unsafe_code)]
 pub mod hint;
 #[forbid(unsafe_code)]
+pub mod inspect;
+#[forbid(unsafe_code)]
 pub mod ntt;
 #[forbid(unsafe_code)]
 pub mod param;
+#[cfg(feature = "prehash")]
+#[forbid(unsafe_code)]
+pub mod prehash;
+#[forbid(unsafe_code)]
+pub mod prepared;
 #[forbid(unsafe_code)]
 pub mod sampling;
 #[forbid(unsafe_code)]
+pub mod seed;
+#[cfg(feature = "self-test")]
+#[forbid(unsafe_code)]
+pub mod self_test;
+#[forbid(unsafe_code)]
+pub mod stream;
+#[forbid(unsafe_code)]
 pub mod util;
+#[forbid(unsafe_code)]
+pub mod validate;
 
-use signature::{Error, SignatureEncoding};
+use core::fmt;
+use std::sync::OnceLock;
 
-/// ML-DSA signature
-#[derive(Clone)]
-pub struct Signature<const N: usize>([u8; N]);
+use hybrid_array::typenum::Unsigned;
+use signature::{Error, Keypair, SignatureEncoding, Signer, Verifier};
+
+#[cfg(feature = "rand_core")]
+use {
+    rand_core::{CryptoRng, TryCryptoRng},
+    signature::{RandomizedMultipartSigner, RandomizedSigner},
+};
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
-/// ML-DSA signing key
-pub struct SigningKey<const N: usize>([u8; N]);
+use crate::algebra::{AlgebraExt, NttMatrix, Vector};
+use crate::crypto::H;
+use crate::hint::Hint;
+use crate::ntt::{Ntt, NttInverse};
+use crate::param::{EncodedCTilde, EncodedSignature, EncodedSigningKey, EncodedVerifyingKey};
+use crate::sampling::{expand_a, expand_s};
+#[cfg(feature = "rand_core")]
+use crate::stream::MuHasher;
+use crate::util::ct_eq;
 
-/// ML-DSA verification key
-pub struct VerificationKey<const N: usize>([u8; N]);
+pub use crate::any::{Algorithm, AnySignature, AnySigningKey, AnyVerifyingKey};
+pub use crate::batch::{BatchItem, verify_batch, verify_batch_all};
+pub use crate::error::MlDsaError;
+pub use crate::inspect::{SignatureReport, SignatureViolation};
+pub use crate::param::{MlDsa44, MlDsa65, MlDsa87, MlDsaParams, ParameterSet};
+#[cfg(feature = "prehash")]
+pub use crate::prehash::PreHash;
+pub use crate::prepared::{PreparedSigningKey, PreparedVerifyingKey};
+pub use crate::seed::{SeedSigningKey, SeedUnavailable};
+pub use crate::stream::{StreamingSigner, StreamingVerifier};
+pub use crate::util::{B32, B64};
+pub use crate::validate::KeyValidationError;
 
-impl<const N: usize> SignatureEncoding for Signature<N> {
-    type Repr = [u8; N];
+// The public key value t = NTT^-1(Â * NTT(s1)) + s2 (Algorithm 6 line 5)
+fn compute_t<P: MlDsaParams>(
+    a_hat: &NttMatrix<P::K, P::L>,
+    s1: &Vector<P::L>,
+    s2: &Vector<P::K>,
+) -> Vector<P::K> {
+    &(a_hat * &s1.ntt()).ntt_inverse() + s2
 }
 
-impl<const N: usize> AsRef<[u8]> for Signature<N> {
-    fn as_ref(&self) -> &[u8] {
-        &self.0
+// With the `self-test` feature, run the power-up self-tests on first use, and fail if the
+// module is in the error state
+fn ensure_operational() -> Result<(), MlDsaError> {
+    #[cfg(feature = "self-test")]
+    self_test::ensure()?;
+    Ok(())
+}
+
+// The message representative mu = H(tr || M') (Algorithm 7 line 6, Algorithm 8 line 7)
+fn message_representative(tr: &B64, mp: &[&[u8]]) -> B64 {
+    let mut h = H::default().absorb(tr);
+    for m in mp {
+        h = h.absorb(m);
     }
+    h.squeeze_new()
+}
+
+/// An ML-DSA signature
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature<P: MlDsaParams> {
+    c_tilde: EncodedCTilde<P>,
+    z: Vector<P::L>,
+    h: Hint<P::K>,
 }
 
-impl<const N: usize> From<[u8; N]> for Signature<N> {
-    fn from(bytes: [u8; N]) -> Self {
-        Self(bytes)
+impl<P: MlDsaParams> Signature<P> {
+    /// Encode the signature as bytes (Algorithm 26 sigEncode)
+    pub fn encode(&self) -> EncodedSignature<P> {
+        let z = P::encode_z(&self.z);
+        let h = P::encode_hint(&self.h);
+        P::concat_sig(self.c_tilde.clone(), z, h)
     }
+
+    /// Decode a signature from bytes (Algorithm 27 sigDecode)
+    ///
+    /// Returns `None` if the hint is not canonically encoded.  The bound on `z` is checked
+    /// during verification.
+    pub fn decode(enc: &EncodedSignature<P>) -> Option<Self> {
+        let (c_tilde, z, h) = P::split_sig(enc);
+
+        let c_tilde = c_tilde.clone();
+        let z = P::decode_z(z)?;
+        let h = P::decode_hint(h)?;
+
+        Some(Self { c_tilde, z, h })
+    }
+}
+
+impl<P: MlDsaParams> TryFrom<&[u8]> for Signature<P> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let enc = EncodedSignature::<P>::try_from(bytes).map_err(|_| MlDsaError::InvalidLength)?;
+        // Every bit pattern of z is in its packed range, so only the hint can be malformed
+        Self::decode(&enc).ok_or_else(|| MlDsaError::NonCanonicalHint.into())
+    }
+}
+
+impl<P: MlDsaParams> From<Signature<P>> for EncodedSignature<P> {
+    fn from(sig: Signature<P>) -> Self {
+        sig.encode()
+    }
+}
+
+impl<P: MlDsaParams> SignatureEncoding for Signature<P> {
+    type Repr = EncodedSignature<P>;
 }
 
-impl<const N: usize> From<Signature<N>> for [u8; N] {
-    fn from(sig: Signature<N>) -> [u8; N] {
-        sig.0
+/// An ML-DSA signing key
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct SigningKey<P: MlDsaParams> {
+    rho: B32,
+    K: B32,
+    tr: B64,
+    s1: Vector<P::L>,
+    s2: Vector<P::K>,
+    t0: Vector<P::K>,
+    // The prepared form of the key, computed by the first signature and reused by later ones.
+    // Boxed, as it is several times the size of the key itself.
+    prepared: OnceLock<Box<PreparedSigningKey<P>>>,
+}
+
+impl<P: MlDsaParams> fmt::Debug for SigningKey<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey").finish_non_exhaustive()
+    }
+}
+
+/// Keys are equal if their encodings are, which is checked in constant time
+impl<P: MlDsaParams> PartialEq for SigningKey<P> {
+    fn eq(&self, other: &Self) -> bool {
+        #[allow(unused_mut)]
+        let (mut a, mut b) = (self.encode(), other.encode());
+        let eq = ct_eq(&a, &b);
+
+        #[cfg(feature = "zeroize")]
+        {
+            a.zeroize();
+            b.zeroize();
+        }
+
+        eq
+    }
+}
+
+impl<P: MlDsaParams> SigningKey<P> {
+    /// Encode the signing key as bytes (Algorithm 24 skEncode)
+    pub fn encode(&self) -> EncodedSigningKey<P> {
+        let s1 = P::encode_s1(&self.s1);
+        let s2 = P::encode_s2(&self.s2);
+        let t0 = P::encode_t0(&self.t0);
+        P::concat_sk(self.rho, self.K, self.tr, s1, s2, t0)
+    }
+
+    /// Decode a signing key from bytes (Algorithm 25 skDecode)
+    ///
+    /// Returns `None` if a coefficient of `s1` or `s2` lies outside `[-eta, eta]`.  The
+    /// encoding of `t0` covers exactly its range, so it cannot be out of range.
+    #[allow(non_snake_case)]
+    pub fn decode(enc: &EncodedSigningKey<P>) -> Option<Self> {
+        let (rho, K, tr, s1, s2, t0) = P::split_sk(enc);
+        Some(Self {
+            rho: *rho,
+            K: *K,
+            tr: *tr,
+            s1: P::decode_s1(s1)?,
+            s2: P::decode_s2(s2)?,
+            t0: P::decode_t0(t0)?,
+            prepared: OnceLock::new(),
+        })
+    }
+
+    /// Derive the verifying key that corresponds to this signing key
+    ///
+    /// A signing key only holds the low-order part `t0` of the public key value, so this
+    /// recomputes `t` from `rho`, `s1`, and `s2`.
+    pub fn verifying_key(&self) -> VerifyingKey<P> {
+        let a_hat = expand_a::<P::K, P::L>(&self.rho);
+        let t = compute_t::<P>(&a_hat, &self.s1, &self.s2);
+        let (t1, _) = t.power2round();
+
+        VerifyingKey { rho: self.rho, t1 }
+    }
+
+    /// Sign the message `M'`, given as a sequence of byte strings, with the per-signature
+    /// randomness `rnd` (Algorithm 7 ML-DSA.Sign_internal)
+    ///
+    /// This is the internal interface: `M'` is not prefixed with a domain separator or
+    /// context.  Use [`SigningKey::sign_randomized`] or [`SigningKey::sign_deterministic`] to
+    /// sign a message with a context string.  See [`SigningKey::try_sign_mu`] for the errors.
+    pub fn try_sign_internal(&self, mp: &[&[u8]], rnd: &B32) -> Result<Signature<P>, Error> {
+        let mu = message_representative(&self.tr, mp);
+        self.try_sign_mu(&mu, rnd)
+    }
+
+    /// Sign the externally computed message representative `mu = H(tr || M')` with the
+    /// per-signature randomness `rnd` (Algorithm 7 ML-DSA.Sign_internal, from line 7 on)
+    ///
+    /// FIPS 204 allows `mu` to be computed by a different module than the one that holds the
+    /// signing key; see [`VerifyingKey::compute_mu`].  The first signature prepares the key,
+    /// as [`SigningKey::prepare`] does, and later signatures reuse the prepared key.
+    ///
+    /// With the `self-test` feature, returns an error if a self-test has failed.  With the
+    /// `verify-after-sign` feature, returns an error if the signature does not verify.
+    pub fn try_sign_mu(&self, mu: &B64, rnd: &B32) -> Result<Signature<P>, Error> {
+        self.prepared().try_sign_mu(mu, rnd)
+    }
+
+    fn prepared(&self) -> &PreparedSigningKey<P> {
+        self.prepared.get_or_init(|| Box::new(self.prepare()))
+    }
+
+    /// Sign the externally computed message representative `mu`, using fresh randomness from
+    /// `rng`
+    ///
+    /// Returns an error under the same conditions as [`SigningKey::try_sign_mu`].
+    #[cfg(feature = "rand_core")]
+    pub fn sign_mu_randomized<R: CryptoRng + ?Sized>(
+        &self,
+        mu: &B64,
+        rng: &mut R,
+    ) -> Result<Signature<P>, Error> {
+        let mut rnd = B32::default();
+        rng.fill_bytes(&mut rnd);
+        self.try_sign_mu(mu, &rnd)
+    }
+
+    /// Sign the externally computed message representative `mu`, deterministically
+    ///
+    /// Returns an error under the same conditions as [`SigningKey::try_sign_mu`].
+    pub fn sign_mu_deterministic(&self, mu: &B64) -> Result<Signature<P>, Error> {
+        self.try_sign_mu(mu, &B32::default())
+    }
+
+    /// Sign the message `m` with the context string `ctx`, using fresh randomness from `rng`
+    /// (Algorithm 2 ML-DSA.Sign, hedged variant)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    #[cfg(feature = "rand_core")]
+    pub fn sign_randomized<R: CryptoRng + ?Sized>(
+        &self,
+        m: &[u8],
+        ctx: &[u8],
+        rng: &mut R,
+    ) -> Result<Signature<P>, Error> {
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+
+        let mut rnd = B32::default();
+        rng.fill_bytes(&mut rnd);
+
+        self.try_sign_internal(&[&[0], &[ctx_len], ctx, m], &rnd)
+    }
+
+    /// Sign the message `m` with the context string `ctx`, deterministically (Algorithm 2
+    /// ML-DSA.Sign, deterministic variant with `rnd = 0^32`)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    pub fn sign_deterministic(&self, m: &[u8], ctx: &[u8]) -> Result<Signature<P>, Error> {
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+        self.try_sign_internal(&[&[0], &[ctx_len], ctx, m], &B32::default())
     }
 }
 
-impl<const N: usize> TryFrom<&[u8]> for Signature<N> {
+impl<P: MlDsaParams> TryFrom<&[u8]> for SigningKey<P> {
     type Error = Error;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Error> {
-        if bytes.len() == N {
-            let mut array = [0u8; N];
-            array.copy_from_slice(bytes);
-            Ok(Self(array))
+        #[allow(unused_mut)]
+        let mut enc =
+            EncodedSigningKey::<P>::try_from(bytes).map_err(|_| MlDsaError::InvalidLength)?;
+        let sk = Self::decode(&enc);
+
+        #[cfg(feature = "zeroize")]
+        enc.zeroize();
+
+        sk.ok_or_else(|| MlDsaError::CoefficientOutOfRange.into())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<P: MlDsaParams> Drop for SigningKey<P> {
+    fn drop(&mut self) {
+        self.rho.zeroize();
+        self.K.zeroize();
+        self.tr.zeroize();
+        self.s1.zeroize();
+        self.s2.zeroize();
+        self.t0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<P: MlDsaParams> ZeroizeOnDrop for SigningKey<P> {}
+
+/// An ML-DSA verifying key
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifyingKey<P: MlDsaParams> {
+    rho: B32,
+    t1: Vector<P::K>,
+}
+
+impl<P: MlDsaParams> VerifyingKey<P> {
+    /// Encode the verifying key as bytes (Algorithm 22 pkEncode)
+    pub fn encode(&self) -> EncodedVerifyingKey<P> {
+        let t1 = P::encode_t1(&self.t1);
+        P::concat_vk(self.rho, t1)
+    }
+
+    /// Decode a verifying key from bytes (Algorithm 23 pkDecode)
+    ///
+    /// Every encoding of the right length is a valid verifying key.
+    pub fn decode(enc: &EncodedVerifyingKey<P>) -> Self {
+        let (rho, t1) = P::split_vk(enc);
+        Self {
+            rho: *rho,
+            t1: P::decode_t1(t1),
+        }
+    }
+
+    /// Verify a signature on the message `M'`, given as a sequence of byte strings
+    /// (Algorithm 8 ML-DSA.Verify_internal)
+    ///
+    /// This is the internal interface: `M'` is not prefixed with a domain separator or
+    /// context.  Use [`VerifyingKey::verify_with_context`] to verify a signature on a message
+    /// with a context string.
+    ///
+    /// With the `self-test` feature, returns `false` if a self-test has failed.
+    pub fn verify_internal(&self, mp: &[&[u8]], sigma: &Signature<P>) -> bool {
+        let mu = self.compute_mu_internal(mp);
+        self.verify_mu(&mu, sigma)
+    }
+
+    /// Verify a signature on the externally computed message representative
+    /// `mu = H(tr || M')` (Algorithm 8 ML-DSA.Verify_internal, from line 7 on)
+    ///
+    /// To verify many signatures with the same key, use a [`PreparedVerifyingKey`] instead.
+    /// With the `self-test` feature, returns `false` if a self-test has failed.
+    pub fn verify_mu(&self, mu: &B64, sigma: &Signature<P>) -> bool {
+        self.prepare().verify_mu(mu, sigma)
+    }
+
+    /// The public key hash `tr = H(pk)`
+    fn tr(&self) -> B64 {
+        H::default().absorb(&self.encode()).squeeze_new()
+    }
+
+    /// Compute the message representative `mu = H(tr || M')` for the message `M'`, given as a
+    /// sequence of byte strings
+    pub fn compute_mu_internal(&self, mp: &[&[u8]]) -> B64 {
+        message_representative(&self.tr(), mp)
+    }
+
+    /// Compute the message representative `mu = H(tr || 0 || |ctx| || ctx || m)` for the
+    /// message `m` and context string `ctx`, as used by ML-DSA.Sign and ML-DSA.Verify
+    ///
+    /// The result can be passed to [`SigningKey::try_sign_mu`] and [`VerifyingKey::verify_mu`],
+    /// so that only 64 bytes need to reach the module that holds the signing key.  Returns an
+    /// error if `ctx` is longer than 255 bytes.
+    pub fn compute_mu(&self, m: &[u8], ctx: &[u8]) -> Result<B64, Error> {
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+        Ok(self.compute_mu_internal(&[&[0], &[ctx_len], ctx, m]))
+    }
+
+    /// Verify a signature on the message `m` with the context string `ctx` (Algorithm 3
+    /// ML-DSA.Verify)
+    ///
+    /// Returns an error if the signature is invalid or `ctx` is longer than 255 bytes, or, with
+    /// the `self-test` feature, if a self-test has failed.
+    pub fn verify_with_context(
+        &self,
+        m: &[u8],
+        ctx: &[u8],
+        sigma: &Signature<P>,
+    ) -> Result<(), Error> {
+        ensure_operational()?;
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+
+        if self.verify_internal(&[&[0], &[ctx_len], ctx, m], sigma) {
+            Ok(())
         } else {
-            Err(Error::new())
+            Err(MlDsaError::InvalidSignature.into())
         }
     }
 }
+
+impl<P: MlDsaParams> TryFrom<&[u8]> for VerifyingKey<P> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let enc =
+            EncodedVerifyingKey::<P>::try_from(bytes).map_err(|_| MlDsaError::InvalidLength)?;
+        Ok(Self::decode(&enc))
+    }
+}
+
+/// An ML-DSA key pair
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyPair<P: MlDsaParams> {
+    signing_key: SigningKey<P>,
+    verifying_key: VerifyingKey<P>,
+}
+
+impl<P: MlDsaParams> KeyPair<P> {
+    /// The signing key of the key pair
+    pub fn signing_key(&self) -> &SigningKey<P> {
+        &self.signing_key
+    }
+
+    /// The verifying key of the key pair
+    pub fn verifying_key(&self) -> &VerifyingKey<P> {
+        &self.verifying_key
+    }
+
+    /// Split the key pair into its signing and verifying keys
+    pub fn into_parts(self) -> (SigningKey<P>, VerifyingKey<P>) {
+        (self.signing_key, self.verifying_key)
+    }
+}
+
+/// Deterministic signing with an empty context string
+impl<P: MlDsaParams> Signer<Signature<P>> for SigningKey<P> {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<P>, Error> {
+        self.sign_deterministic(msg, &[])
+    }
+}
+
+/// Hedged signing with an empty context string
+#[cfg(feature = "rand_core")]
+impl<P: MlDsaParams> RandomizedSigner<Signature<P>> for SigningKey<P> {
+    fn try_sign_with_rng<R: TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        msg: &[u8],
+    ) -> Result<Signature<P>, Error> {
+        self.try_multipart_sign_with_rng(rng, &[msg])
+    }
+}
+
+/// Hedged signing of a message given as a sequence of chunks, with an empty context string
+#[cfg(feature = "rand_core")]
+impl<P: MlDsaParams> RandomizedMultipartSigner<Signature<P>> for SigningKey<P> {
+    fn try_multipart_sign_with_rng<R: TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        msg: &[&[u8]],
+    ) -> Result<Signature<P>, Error> {
+        let mut rnd = B32::default();
+        rng.try_fill_bytes(&mut rnd)
+            .map_err(|_| MlDsaError::RngFailure)?;
+
+        let mut mu = MuHasher::new(&self.tr, &[])?;
+        for chunk in msg {
+            mu.update(chunk);
+        }
+
+        self.try_sign_mu(&mu.finish(), &rnd)
+    }
+}
+
+impl<P: MlDsaParams> Keypair for SigningKey<P> {
+    type VerifyingKey = VerifyingKey<P>;
+
+    fn verifying_key(&self) -> VerifyingKey<P> {
+        SigningKey::verifying_key(self)
+    }
+}
+
+/// Verification with an empty context string
+impl<P: MlDsaParams> Verifier<Signature<P>> for VerifyingKey<P> {
+    fn verify(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), Error> {
+        self.verify_with_context(msg, &[], signature)
+    }
+}
+
+impl<P: MlDsaParams> Signer<Signature<P>> for KeyPair<P> {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<P>, Error> {
+        self.signing_key.try_sign(msg)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<P: MlDsaParams> RandomizedSigner<Signature<P>> for KeyPair<P> {
+    fn try_sign_with_rng<R: TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        msg: &[u8],
+    ) -> Result<Signature<P>, Error> {
+        self.signing_key.try_sign_with_rng(rng, msg)
+    }
+}
+
+impl<P: MlDsaParams> Keypair for KeyPair<P> {
+    type VerifyingKey = VerifyingKey<P>;
+
+    fn verifying_key(&self) -> VerifyingKey<P> {
+        self.verifying_key.clone()
+    }
+}
+
+/// Key generation for an ML-DSA parameter set
+pub trait KeyGen: MlDsaParams {
+    /// Generate a key pair from a fresh random seed (Algorithm 1 ML-DSA.KeyGen)
+    ///
+    /// Returns an error if `rng` fails, or, with the `self-test` feature, if a self-test fails.
+    #[cfg(feature = "rand_core")]
+    fn key_gen<R: TryCryptoRng + ?Sized>(rng: &mut R) -> Result<KeyPair<Self>, Error>;
+
+    /// Deterministically derive a key pair from the seed `xi` (Algorithm 6
+    /// ML-DSA.KeyGen_internal)
+    ///
+    /// The same seed always produces the same key pair, byte for byte, so a key pair can be
+    /// stored as its 32-byte seed and recreated on demand.
+    ///
+    /// With the `self-test` feature, returns an error if a self-test fails, including the
+    /// pairwise consistency test of the new key pair.
+    fn key_gen_internal(xi: &B32) -> Result<KeyPair<Self>, Error>;
+}
+
+impl<P: MlDsaParams> KeyGen for P {
+    #[cfg(feature = "rand_core")]
+    fn key_gen<R: TryCryptoRng + ?Sized>(rng: &mut R) -> Result<KeyPair<P>, Error> {
+        let mut xi = B32::default();
+        rng.try_fill_bytes(&mut xi)
+            .map_err(|_| MlDsaError::RngFailure)?;
+        let kp = Self::key_gen_internal(&xi);
+
+        #[cfg(feature = "zeroize")]
+        xi.zeroize();
+
+        kp
+    }
+
+    fn key_gen_internal(xi: &B32) -> Result<KeyPair<P>, Error> {
+        ensure_operational()?;
+        let kp = key_gen_unchecked(xi);
+
+        #[cfg(feature = "self-test")]
+        self_test::check_key_pair(&kp)?;
+
+        Ok(kp)
+    }
+}
+
+// Algorithm 6 ML-DSA.KeyGen_internal, without the self-tests
+fn key_gen_unchecked<P: MlDsaParams>(xi: &B32) -> KeyPair<P> {
+    // Derive seeds
+    let mut h = H::default()
+        .absorb(xi)
+        .absorb(&[P::K::U8])
+        .absorb(&[P::L::U8])
+        .finalize();
+
+    let rho: B32 = h.squeeze_new();
+    let rhop: B64 = h.squeeze_new();
+    #[allow(non_snake_case)]
+    let K: B32 = h.squeeze_new();
+
+    // Sample the secret vectors and expand the public matrix
+    let a_hat = expand_a::<P::K, P::L>(&rho);
+    let s1 = expand_s::<P::L, P::Eta>(&rhop, 0);
+    let s2 = expand_s::<P::K, P::Eta>(&rhop, P::L::USIZE);
+
+    // Compute the public key value and split it into high and low bits
+    let t = compute_t::<P>(&a_hat, &s1, &s2);
+    let (t1, t0) = t.power2round();
+
+    let verifying_key = VerifyingKey { rho, t1 };
+
+    // tr = H(pk)
+    let tr: B64 = H::default().absorb(&verifying_key.encode()).squeeze_new();
+
+    let signing_key = SigningKey {
+        rho: verifying_key.rho,
+        K,
+        tr,
+        s1,
+        s2,
+        t0,
+        prepared: OnceLock::new(),
+    };
+
+    KeyPair {
+        signing_key,
+        verifying_key,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::{D, Elem};
+    use crate::param::{SigningKeyParams, T0Max};
+    use hex_literal::hex;
+    use sha3::{Digest, Sha3_256};
+
+    const SEED: [u8; 32] = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+
+    fn key_gen_properties<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        // Deterministic from the seed, and sensitive to it
+        assert_eq!(kp, P::key_gen_internal(&B32::from(SEED)).unwrap());
+        assert_ne!(kp, P::key_gen_internal(&B32::default()).unwrap());
+
+        // The secret vectors and t0 lie in their ranges
+        assert!(sk.s1.infinity_norm() <= P::Eta::U32);
+        assert!(sk.s2.infinity_norm() <= P::Eta::U32);
+        assert!(sk.t0.infinity_norm() <= T0Max::U32);
+
+        // t1 * 2^d + t0 = A * s1 + s2
+        let a_hat = expand_a::<P::K, P::L>(&vk.rho);
+        let t = &(&a_hat * &sk.s1.ntt()).ntt_inverse() + &sk.s2;
+        let t1_2d = Elem::new(1 << D) * &vk.t1;
+        assert_eq!(&t1_2d + &sk.t0, t);
+
+        // tr = H(pk)
+        let tr: B64 = H::default().absorb(&vk.encode()).squeeze_new();
+        assert_eq!(sk.tr, tr);
+        assert_eq!(sk.rho, vk.rho);
+    }
+
+    #[test]
+    fn key_gen_internal_properties() {
+        key_gen_properties::<MlDsa44>();
+        key_gen_properties::<MlDsa65>();
+        key_gen_properties::<MlDsa87>();
+    }
+
+    // The ML-DSA-44 verifying key that OpenSSL 3.5 derives from `SEED`, in full
+    const PK44: [u8; 1312] = hex!(
+        "d7b2b47254aae0db45e7930d4a98d2c97d8f1397d1789dafa17024b316e9bec94fc9946d42f19b79a7413bbaa33e7149"
+        "cb42ed5115693ac041facb988adeb5fe0e1d8631184995b592c397d2294e2e14f90aa414ba3826899ac43f4cccacbc26"
+        "e9a832b95118d5cb433cbef9660b00138e0817f61e762ca274c36ad554eb22aac1162e4ab01acba1e38c4efd8f80b65b"
+        "333d0f72e55dfe71ce9c1ebb9889e7c56106c0fd73803a2aecfeafded7aa3cb2ceda54d12bd8cd36a78cf975943b47ab"
+        "d25e880ac452e5742ed1e8d1a82afa86e590c758c15ae4d2840d92bca1a5090f40496597fca7d8b9513f1a1bda6e950a"
+        "aa98de467507d4a4f5a4f0599216582c3572f62eda8905ab3581670c4a02777a33e0ca7295fd8f4ff6d1a0a3a7683d65"
+        "f5f5f7fc60da023e826c5f92144c02f7d1ba1075987553ea9367fcd76d990b7fa99cd45afdb8836d43e459f5187df058"
+        "479709a01ea6835935fa70460990cd3dc1ba401ba94bab1dde41ac67ab3319dcaca06048d4c4eef27ee13a9c17d0538f"
+        "430f2d642dc2415660de78877d8d8abc72523978c042e4285f4319846c44126242976844c10e556ba215b5a719e59d0c"
+        "6b2a96d39859071fdcc2cde7524a7bedae54e85b318e854e8fe2b2f3edfac9719128270aafd1e5044c3a4fdafd9ff31f"
+        "90784b8e8e4596144a0daf586511d3d9962b9ea95af197b4e5fc60f2b1ed15de3a5bef5f89bdc79d91051d9b2816e74f"
+        "a54531efdc1cbe74d448857f476bcd58f21c0b653b3b76a4e076a6559a302718555cc63f74859aabab925f023861ca8c"
+        "d0f7badb2871f67d55326d7451135ad45f4a1ba69118fbb2c8a30eec9392ef3f977066c9add5c710cc647b1514d217d9"
+        "58c7017c3e90fd20c04e674b90486e9370a31a001d32f473979e4906749e7e477fa0b74508f8a5f2378312b83c25bd38"
+        "8ca0b0fff7478baf42b71667edaac97c46b129643e586e5b055a0c211946d4f36e675bed5860fa042a315d9826164d6a"
+        "9237c35a5fbf495490a5bd4df248b95c4aae7784b605673166ac4245b5b4b082a09e9323e62f2078c5b76783446defd7"
+        "36ad3a3702d49b089844900a61833397bc4419b30d7a97a0b387c1911474c4d41b53e32a977acb6f0ea75db65bb39e59"
+        "e701e76957def6f2d44559c31a77122b5204e3b5c219f1688b14ed0bc0b801b3e6e82dcd43e9c0e9f41744cd9815bd1b"
+        "c8820d8bb123f04facd1b1b685dd5a2b1b8dbbf3ed933670f095a180b4f192d08b10b8fabbdfcc2b24518e32eea0a5e0"
+        "c904ca844780083f3b0cd2d0b8b6af67bc355b9494025dc7b0a78fa80e3a2dbfeb51328851d6078198e9493651ae787e"
+        "c0251f922ba30e9f51df62a6d72784cf3dd205393176dfa324a512bd94970a36dd34a514a86791f0eb36f0145b09ab64"
+        "651b4a0313b299611a2a1c48891627598768a3114060ba4443486df51522a1ce88b30985c216f8e6ed178dd567b304a0"
+        "d4cafba882a28342f17a9aa26ae58db630083d2c358fdf566c3f5d62a428567bc9ea8ce95caa0f35474b0bfa8f339a25"
+        "0ab4dfcf2083be8eefbc1055e18fe15370eecb260566d83ff06b211aaec43ca29b54ccd00f8815a2465ef0b46515cc7e"
+        "41f3124f09efff739309ab58b29a1459a00bce5038e938c9678f72eb0e4ee5fdaae66d9f8573fc97fc42b4959f4bf8b6"
+        "1d78433e86b0335d6e9191c4d8bf487b3905c108cfd6ac24b0ceb7dcb7cf51f84d0ed687b95eaeb1c533c06f0d97023d"
+        "92a70825837b59ba6cb7d4e56b0a87c203862ae8f315ba5925e8edefa679369a2202766151f16a965f9f81ece76cc070"
+        "b55869e4db9784cf05c830b3242c8312"
+    );
+
+    // SHA3-256 digests of the verifying keys that an independent FIPS 204 implementation
+    // (OpenSSL 3.5) derives from `SEED`
+    fn key_gen_kat<P: MlDsaParams>(expected: [u8; 32]) {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let pk = kp.verifying_key().encode();
+        assert_eq!(pk.len(), P::VerifyingKeySize::USIZE);
+        assert_eq!(Sha3_256::digest(&pk).as_slice(), expected);
+    }
+
+    #[test]
+    fn key_gen_internal_kat() {
+        let kp = MlDsa44::key_gen_internal(&B32::from(SEED)).unwrap();
+        assert_eq!(kp.verifying_key().encode().as_slice(), PK44);
+
+        key_gen_kat::<MlDsa44>(hex!(
+            "373c7bf2cac5bd2a6c35933bab0fa1c951f22247e1333383fcb618822080373f"
+        ));
+        key_gen_kat::<MlDsa65>(hex!(
+            "1800725067e388d837d911fe4f66101cc1961b1bb755030dc574272cfb00013f"
+        ));
+        key_gen_kat::<MlDsa87>(hex!(
+            "e6cf50a9c2fa5234f59949ff61f8161db4d629532127f4aefa8bb10811ecfb1e"
+        ));
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn key_gen_randomized() {
+        let mut rng = rand::rng();
+        let kp1 = MlDsa44::key_gen(&mut rng).unwrap();
+        let kp2 = MlDsa44::key_gen(&mut rng).unwrap();
+        assert_ne!(kp1.verifying_key(), kp2.verifying_key());
+
+        let (sk, vk) = kp1.into_parts();
+        assert_eq!(sk.rho, vk.rho);
+    }
+
+    const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    // SHA3-256 digests of deterministic signatures on `MSG` under the keys derived from `SEED`,
+    // with the context `01020304` and with an empty context, as produced by OpenSSL 3.5
+    fn sign_kat<P: MlDsaParams>(with_ctx: [u8; 32], without_ctx: [u8; 32]) {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let sk = kp.signing_key();
+
+        let sig = sk.sign_deterministic(MSG, &[1, 2, 3, 4]).unwrap();
+        let enc = sig.encode();
+        assert_eq!(enc.len(), P::SignatureSize::USIZE);
+        assert_eq!(Sha3_256::digest(&enc).as_slice(), with_ctx);
+
+        let sig = sk.sign_deterministic(MSG, &[]).unwrap();
+        assert_eq!(Sha3_256::digest(sig.encode()).as_slice(), without_ctx);
+    }
+
+    #[test]
+    fn sign_deterministic_kat() {
+        sign_kat::<MlDsa44>(
+            hex!("65abbc39edbeace4c8108539c371f1b1b13dd4c1c52dfb910c48452d56e10a09"),
+            hex!("8c320870c58c4283a268d94f625e347b4766e1424c2e2f200965a83dd46cd252"),
+        );
+        sign_kat::<MlDsa65>(
+            hex!("2eae94356f3bef833b9b78a5e94e073ac0e03418fbebc43c76ad815b58f91db7"),
+            hex!("73065a768ff7f2e731653d776d17a04cc13e2dce282c6079302cb549f8692363"),
+        );
+        sign_kat::<MlDsa87>(
+            hex!("1e23e969da2238edb27cbcb2e61b923520f3f3de4b3fa6d58e01c5297e00f61b"),
+            hex!("cfcba77d2894e36107e3358f75946caf0d7dd93eb583af433dc796cb010e729c"),
+        );
+    }
+
+    fn sign_properties<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let sk = kp.signing_key();
+
+        for i in 0..8u8 {
+            let sig = sk.try_sign_internal(&[MSG], &B32::from([i; 32])).unwrap();
+            assert!(sig.z.infinity_norm() < P::GAMMA1_MINUS_BETA);
+            assert!(sig.h.hamming_weight() <= P::Omega::USIZE);
+        }
+
+        // The deterministic variant is Sign_internal with rnd = 0^32 and M' = 0 || |ctx| || ctx || M
+        let ctx = b"ctx";
+        assert_eq!(
+            sk.sign_deterministic(MSG, ctx).unwrap(),
+            sk.try_sign_internal(&[&[0, 3], ctx, MSG], &B32::default())
+                .unwrap()
+        );
+
+        // Different randomness gives different signatures
+        assert_ne!(
+            sk.try_sign_internal(&[MSG], &B32::from([1; 32])).unwrap(),
+            sk.try_sign_internal(&[MSG], &B32::from([2; 32])).unwrap()
+        );
+    }
+
+    #[test]
+    fn sign_internal_properties() {
+        sign_properties::<MlDsa44>();
+        sign_properties::<MlDsa65>();
+        sign_properties::<MlDsa87>();
+    }
+
+    #[test]
+    fn sign_context_length() {
+        let kp = MlDsa44::key_gen_internal(&B32::from(SEED)).unwrap();
+        let sk = kp.signing_key();
+
+        assert!(sk.sign_deterministic(MSG, &[0; 255]).is_ok());
+        assert!(sk.sign_deterministic(MSG, &[0; 256]).is_err());
+
+        #[cfg(feature = "rand_core")]
+        {
+            let mut rng = rand::rng();
+            assert!(sk.sign_randomized(MSG, &[0; 255], &mut rng).is_ok());
+            assert!(sk.sign_randomized(MSG, &[0; 256], &mut rng).is_err());
+        }
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn sign_randomized() {
+        let mut rng = rand::rng();
+        let kp = MlDsa65::key_gen(&mut rng).unwrap();
+        let sk = kp.signing_key();
+
+        let sig1 = sk.sign_randomized(MSG, b"ctx", &mut rng).unwrap();
+        let sig2 = sk.sign_randomized(MSG, b"ctx", &mut rng).unwrap();
+        assert_ne!(sig1, sig2);
+    }
+
+    fn verify_round_trip<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        let sig = sk.sign_deterministic(MSG, b"ctx").unwrap();
+        assert!(vk.verify_with_context(MSG, b"ctx", &sig).is_ok());
+
+        // Wrong message, wrong context, or wrong key
+        assert!(vk.verify_with_context(b"other", b"ctx", &sig).is_err());
+        assert!(vk.verify_with_context(MSG, b"", &sig).is_err());
+        let other = P::key_gen_internal(&B32::default()).unwrap();
+        assert!(
+            other
+                .verifying_key()
+                .verify_with_context(MSG, b"ctx", &sig)
+                .is_err()
+        );
+
+        // Encoded signatures decode to the same value and still verify
+        let enc = sig.encode();
+        let dec = Signature::<P>::try_from(enc.as_slice()).unwrap();
+        assert_eq!(dec, sig);
+        assert!(vk.verify_with_context(MSG, b"ctx", &dec).is_ok());
+
+        // The internal interface verifies what the internal interface signs
+        let sig = sk.try_sign_internal(&[MSG], &B32::from([7; 32])).unwrap();
+        assert!(vk.verify_internal(&[MSG], &sig));
+        assert!(!vk.verify_internal(&[MSG, b"!"], &sig));
+    }
+
+    #[test]
+    fn verify() {
+        verify_round_trip::<MlDsa44>();
+        verify_round_trip::<MlDsa65>();
+        verify_round_trip::<MlDsa87>();
+    }
+
+    #[test]
+    fn verify_context_length() {
+        let kp = MlDsa44::key_gen_internal(&B32::from(SEED)).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        let ctx = [0x5a; 255];
+        let sig = sk.sign_deterministic(MSG, &ctx).unwrap();
+        assert!(vk.verify_with_context(MSG, &ctx, &sig).is_ok());
+        assert!(vk.verify_with_context(MSG, &[0x5a; 256], &sig).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_malformed() {
+        let kp = MlDsa44::key_gen_internal(&B32::from(SEED)).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+        let enc = sk.sign_deterministic(MSG, &[]).unwrap().encode();
+
+        // Wrong lengths
+        assert!(Signature::<MlDsa44>::try_from(&enc[1..]).is_err());
+        assert!(
+            Signature::<MlDsa44>::try_from([enc.as_slice(), &[0]].concat().as_slice()).is_err()
+        );
+        assert!(Signature::<MlDsa44>::try_from(&[][..]).is_err());
+
+        // Flipping a bit anywhere either breaks decoding or verification
+        for i in (0..enc.len()).step_by(37).chain([enc.len() - 1]) {
+            let mut bad = enc;
+            bad[i] ^= 0x01;
+            let result = Signature::<MlDsa44>::try_from(bad.as_slice())
+                .and_then(|sig| vk.verify_with_context(MSG, &[], &sig));
+            assert!(result.is_err(), "bit flip at byte {i} was accepted");
+        }
+
+        // Non-canonical hints are rejected at decoding time
+        let mut bad = enc;
+        let last = bad.len() - 1;
+        bad[last] = 81;
+        assert!(Signature::<MlDsa44>::try_from(bad.as_slice()).is_err());
+
+        // A z coefficient at gamma1 decodes, but exceeds gamma1 - beta
+        let mut sig = Signature::<MlDsa44>::decode(&enc).unwrap();
+        sig.z.0[0].0[0] = Elem::new(1 << 17);
+        assert!(vk.verify_with_context(MSG, &[], &sig).is_err());
+        let sig = Signature::<MlDsa44>::decode(&sig.encode()).unwrap();
+        assert!(vk.verify_with_context(MSG, &[], &sig).is_err());
+    }
+
+    // SHA3-256 digests of the signing keys that OpenSSL 3.5 derives from `SEED`
+    fn encode_kat<P: MlDsaParams>(expected: [u8; 32]) {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let sk = kp.signing_key().encode();
+        assert_eq!(sk.len(), P::SigningKeySize::USIZE);
+        assert_eq!(Sha3_256::digest(&sk).as_slice(), expected);
+    }
+
+    #[test]
+    fn signing_key_encode_kat() {
+        encode_kat::<MlDsa44>(hex!(
+            "da0665a6d807669d06aeb7010507aa6bd4640641055462e38e37344a274f36a6"
+        ));
+        encode_kat::<MlDsa65>(hex!(
+            "4ecfbd119980b1090a7feda39c225539ffa3ef3ff673b239b60189bfd4541170"
+        ));
+        encode_kat::<MlDsa87>(hex!(
+            "db6218a2eeae53e567d25222055251eab0ba85d1c69aeb8a62253d2096fd96a2"
+        ));
+    }
+
+    fn encode_round_trip<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        let sk_enc = sk.encode();
+        let sk2 = SigningKey::<P>::try_from(sk_enc.as_slice()).unwrap();
+        assert_eq!(&sk2, sk);
+        assert_eq!(sk2.encode(), sk_enc);
+
+        let vk_enc = vk.encode();
+        let vk2 = VerifyingKey::<P>::try_from(vk_enc.as_slice()).unwrap();
+        assert_eq!(&vk2, vk);
+        assert_eq!(vk2.encode(), vk_enc);
+
+        // Decoded keys are fully functional
+        let sig = sk2.sign_deterministic(MSG, &[]).unwrap();
+        assert_eq!(sig, sk.sign_deterministic(MSG, &[]).unwrap());
+        assert!(vk2.verify_with_context(MSG, &[], &sig).is_ok());
+
+        // Wrong lengths are rejected
+        assert!(SigningKey::<P>::try_from(&sk_enc[1..]).is_err());
+        assert!(SigningKey::<P>::try_from([sk_enc.as_slice(), &[0]].concat().as_slice()).is_err());
+        assert!(VerifyingKey::<P>::try_from(&vk_enc[1..]).is_err());
+        assert!(
+            VerifyingKey::<P>::try_from([vk_enc.as_slice(), &[0]].concat().as_slice()).is_err()
+        );
+    }
+
+    #[test]
+    fn key_encode_round_trip() {
+        encode_round_trip::<MlDsa44>();
+        encode_round_trip::<MlDsa65>();
+        encode_round_trip::<MlDsa87>();
+    }
+
+    #[test]
+    fn signing_key_decode_rejects_out_of_range() {
+        // eta = 2: 3 bits per coefficient, encoded values above 4 are invalid
+        let sk = MlDsa44::key_gen_internal(&B32::from(SEED))
+            .unwrap()
+            .signing_key()
+            .encode();
+        let s1_start = 128;
+        let s2_start = s1_start + <MlDsa44 as SigningKeyParams>::S1Size::USIZE;
+
+        let mut bad = sk;
+        bad[s1_start] |= 0x07;
+        assert!(SigningKey::<MlDsa44>::try_from(bad.as_slice()).is_err());
+
+        let mut bad = sk;
+        bad[s2_start] |= 0x07;
+        assert!(SigningKey::<MlDsa44>::try_from(bad.as_slice()).is_err());
+
+        // eta = 4: 4 bits per coefficient, encoded values above 8 are invalid
+        let sk = MlDsa65::key_gen_internal(&B32::from(SEED))
+            .unwrap()
+            .signing_key()
+            .encode();
+        let mut bad = sk;
+        bad[s1_start] = 0xff;
+        assert!(SigningKey::<MlDsa65>::try_from(bad.as_slice()).is_err());
+    }
+
+    fn external_mu<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        // Signing mu is the same as signing the message
+        let mu = vk.compute_mu(MSG, b"ctx").unwrap();
+        let sig = sk.sign_mu_deterministic(&mu).unwrap();
+        assert_eq!(sig, sk.sign_deterministic(MSG, b"ctx").unwrap());
+        assert!(vk.verify_with_context(MSG, b"ctx", &sig).is_ok());
+        assert!(vk.verify_mu(&mu, &sig));
+
+        // ... and the verifier can work from mu as well
+        let sig = sk.sign_deterministic(MSG, &[]).unwrap();
+        assert!(vk.verify_mu(&vk.compute_mu(MSG, &[]).unwrap(), &sig));
+        assert!(!vk.verify_mu(&mu, &sig));
+
+        // mu binds the key through tr
+        assert_eq!(vk.tr(), sk.tr);
+        let other = P::key_gen_internal(&B32::default()).unwrap();
+        assert_ne!(other.verifying_key().compute_mu(MSG, b"ctx").unwrap(), mu);
+    }
+
+    #[test]
+    fn sign_verify_external_mu() {
+        external_mu::<MlDsa44>();
+        external_mu::<MlDsa65>();
+        external_mu::<MlDsa87>();
+
+        let kp = MlDsa44::key_gen_internal(&B32::from(SEED)).unwrap();
+        assert!(kp.verifying_key().compute_mu(MSG, &[0; 256]).is_err());
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn sign_mu_randomized() {
+        let mut rng = rand::rng();
+        let kp = MlDsa87::key_gen(&mut rng).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        let mu = vk.compute_mu(MSG, b"ctx").unwrap();
+        let sig = sk.sign_mu_randomized(&mu, &mut rng).unwrap();
+        assert!(vk.verify_with_context(MSG, b"ctx", &sig).is_ok());
+    }
+
+    fn signature_traits<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from(SEED)).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        // Signer / Verifier use the empty context
+        let sig: Signature<P> = sk.sign(MSG);
+        assert_eq!(sig, sk.sign_deterministic(MSG, &[]).unwrap());
+        assert_eq!(sig, Signer::<Signature<P>>::sign(&kp, MSG));
+        assert!(vk.verify(MSG, &sig).is_ok());
+        assert!(vk.verify(b"other", &sig).is_err());
+
+        // Keypair recovers the verifying key from either form
+        assert_eq!(&Keypair::verifying_key(sk), vk);
+        assert_eq!(&Keypair::verifying_key(&kp), vk);
+
+        // SignatureEncoding
+        let bytes = sig.to_bytes();
+        assert_eq!(bytes, sig.encode());
+        assert_eq!(sig.encoded_len(), P::SignatureSize::USIZE);
+        assert_eq!(Signature::<P>::try_from(bytes.as_slice()).unwrap(), sig);
+    }
+
+    #[test]
+    fn signature_crate_traits() {
+        signature_traits::<MlDsa44>();
+        signature_traits::<MlDsa65>();
+        signature_traits::<MlDsa87>();
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn randomized_signer_traits() {
+        let mut rng = rand::rng();
+        let kp = MlDsa44::key_gen(&mut rng).unwrap();
+        let vk = kp.verifying_key();
+
+        let sig: Signature<MlDsa44> = kp.signing_key().sign_with_rng(&mut rng, MSG);
+        assert!(vk.verify(MSG, &sig).is_ok());
+
+        let sig: Signature<MlDsa44> = kp.sign_with_rng(&mut rng, MSG);
+        assert!(vk.verify(MSG, &sig).is_ok());
+
+        let sig: Signature<MlDsa44> = kp
+            .signing_key()
+            .multipart_sign_with_rng(&mut rng, &[&MSG[..3], &MSG[3..]]);
+        assert!(vk.verify(MSG, &sig).is_ok());
+    }
+}
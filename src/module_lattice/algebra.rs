@@ -57,9 +57,12 @@ pub trait Field: Copy + Default + Debug + PartialEq {
 /// * `$longlong`: The primitive integer type to be used to represent products of three field
 ///   members. This type should have roughly four times the bits of `$int`.
 /// * `$q`: The prime number that defines the field.
+///
+/// Any attributes (including doc comments) placed before `$field` are applied to the struct.
 #[macro_export]
 macro_rules! define_field {
-    ($field:ident, $int:ty, $long:ty, $longlong:ty, $q:literal) => {
+    ($(#[$meta:meta])* $field:ident, $int:ty, $long:ty, $longlong:ty, $q:literal) => {
+        $(#[$meta])*
         #[derive(Copy, Clone, Default, Debug, PartialEq)]
         pub struct $field;
 
@@ -1,4 +1,217 @@
 //! Number Theoretic Transform (NTT) operations for ML-DSA
+//!
+//! The NTT maps polynomials in `R_q` to the NTT domain `T_q`, where multiplication is performed
+//! coefficient-wise.  This module provides the forward and inverse transforms (FIPS 204
+//! Algorithms 41 and 42) and `MultiplyNTT` (Algorithm 45), which lets the `module_lattice` NTT
+//! vector and matrix products be used with the ML-DSA base field.
 
-/// NTT implementation
-pub struct NTT;
\ No newline at end of file
+use core::ops::Mul;
+use hybrid_array::ArraySize;
+
+use crate::algebra::{BaseField, Elem, Field, NttPolynomial, NttVector, Polynomial, Vector};
+
+/// Powers of the 512th root of unity `zeta = 1753` in bit-reversed order, i.e.,
+/// `ZETA_POW_BITREV[i] = zeta^BitRev8(i) mod q` (FIPS 204 Appendix B)
+#[allow(clippy::as_conversions)]
+#[allow(clippy::integer_division_remainder_used)]
+pub const ZETA_POW_BITREV: [Elem; 256] = {
+    const ZETA: u64 = 1753;
+    const fn bitrev8(x: usize) -> usize {
+        (x as u8).reverse_bits() as usize
+    }
+
+    // Compute the powers of zeta
+    let mut pow = [Elem::new(0); 256];
+    let mut i = 0;
+    let mut curr = 1u64;
+    while i < 256 {
+        pow[i] = Elem::new(curr as u32);
+        i += 1;
+        curr = (curr * ZETA) % BaseField::QL;
+    }
+
+    // Reorder the powers according to bitrev8
+    let mut pow_bitrev = [Elem::new(0); 256];
+    let mut i = 0;
+    while i < 256 {
+        pow_bitrev[i] = pow[bitrev8(i)];
+        i += 1;
+    }
+    pow_bitrev
+};
+
+/// Conversion into the NTT domain
+pub trait Ntt {
+    /// The NTT-domain representation
+    type Output;
+    /// Apply the forward NTT
+    fn ntt(&self) -> Self::Output;
+}
+
+impl Ntt for Polynomial {
+    type Output = NttPolynomial;
+
+    // Algorithm 41 NTT
+    fn ntt(&self) -> Self::Output {
+        let mut w = self.0;
+
+        let mut m = 0;
+        for len in [128, 64, 32, 16, 8, 4, 2, 1] {
+            for start in (0..256).step_by(2 * len) {
+                m += 1;
+                let z = ZETA_POW_BITREV[m];
+
+                for j in start..(start + len) {
+                    let t = z * w[j + len];
+                    w[j + len] = w[j] - t;
+                    w[j] = w[j] + t;
+                }
+            }
+        }
+
+        NttPolynomial::new(w)
+    }
+}
+
+impl<K: ArraySize> Ntt for Vector<K> {
+    type Output = NttVector<K>;
+
+    fn ntt(&self) -> Self::Output {
+        NttVector::new(self.0.iter().map(Polynomial::ntt).collect())
+    }
+}
+
+/// Conversion out of the NTT domain
+pub trait NttInverse {
+    /// The normal-domain representation
+    type Output;
+    /// Apply the inverse NTT
+    fn ntt_inverse(&self) -> Self::Output;
+}
+
+impl NttInverse for NttPolynomial {
+    type Output = Polynomial;
+
+    // Algorithm 42 NTT^{-1}
+    fn ntt_inverse(&self) -> Self::Output {
+        // 256^{-1} mod q
+        const INVERSE_256: Elem = Elem::new(8_347_681);
+
+        let mut w = self.0;
+
+        let mut m = 256;
+        for len in [1, 2, 4, 8, 16, 32, 64, 128] {
+            for start in (0..256).step_by(2 * len) {
+                m -= 1;
+                let z = -ZETA_POW_BITREV[m];
+
+                for j in start..(start + len) {
+                    let t = w[j];
+                    w[j] = t + w[j + len];
+                    w[j + len] = z * (t - w[j + len]);
+                }
+            }
+        }
+
+        INVERSE_256 * &Polynomial::new(w)
+    }
+}
+
+impl<K: ArraySize> NttInverse for NttVector<K> {
+    type Output = Vector<K>;
+
+    fn ntt_inverse(&self) -> Self::Output {
+        Vector::new(self.0.iter().map(NttPolynomial::ntt_inverse).collect())
+    }
+}
+
+// Algorithm 45 MultiplyNTT
+impl Mul<&NttPolynomial> for &NttPolynomial {
+    type Output = NttPolynomial;
+
+    fn mul(self, rhs: &NttPolynomial) -> NttPolynomial {
+        NttPolynomial::new(
+            self.0
+                .iter()
+                .zip(rhs.0.iter())
+                .map(|(&x, &y)| x * y)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::NttMatrix;
+    use hybrid_array::{Array, typenum::U2};
+
+    // Multiplication in R_q = Z_q[X] / (X^256 + 1), computed the slow way
+    fn schoolbook_mul(lhs: &Polynomial, rhs: &Polynomial) -> Polynomial {
+        let mut out = Polynomial::default();
+        for (i, &x) in lhs.0.iter().enumerate() {
+            for (j, &y) in rhs.0.iter().enumerate() {
+                let k = (i + j) % 256;
+                if i + j < 256 {
+                    out.0[k] = out.0[k] + x * y;
+                } else {
+                    out.0[k] = out.0[k] - x * y;
+                }
+            }
+        }
+        out
+    }
+
+    fn test_poly(seed: u32) -> Polynomial {
+        Polynomial::new(Array::from_fn(|i| {
+            let i = u32::try_from(i).unwrap();
+            Elem::new((seed.wrapping_mul(2_654_435_761) ^ (i * 40_503)) % BaseField::Q)
+        }))
+    }
+
+    #[test]
+    fn zeta_table() {
+        // zeta^BitRev8(i) for the first few i; zeta^128 is a fourth root of unity, and so on
+        let expected = [1, 4808194, 3765607, 3761513, 5178923, 5496691, 5234739];
+        for (z, e) in ZETA_POW_BITREV.iter().zip(expected) {
+            assert_eq!(z.0, e);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for seed in 0..4 {
+            let f = test_poly(seed);
+            assert_eq!(f.ntt().ntt_inverse(), f);
+        }
+
+        let v = Vector::<U2>::new(Array([test_poly(5), test_poly(6)]));
+        assert_eq!(v.ntt().ntt_inverse(), v);
+    }
+
+    #[test]
+    fn multiply() {
+        let f = test_poly(1);
+        let g = test_poly(2);
+
+        let expected = schoolbook_mul(&f, &g);
+        let actual = (&f.ntt() * &g.ntt()).ntt_inverse();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn matrix_vector() {
+        let a = NttMatrix::<U2, U2>::new(Array([
+            NttVector::new(Array([test_poly(1).ntt(), test_poly(2).ntt()])),
+            NttVector::new(Array([test_poly(3).ntt(), test_poly(4).ntt()])),
+        ]));
+        let v = Vector::<U2>::new(Array([test_poly(5), test_poly(6)]));
+
+        let actual = (&a * &v.ntt()).ntt_inverse();
+        for (i, row) in [[1, 2], [3, 4]].iter().enumerate() {
+            let expected = &schoolbook_mul(&test_poly(row[0]), &v.0[0])
+                + &schoolbook_mul(&test_poly(row[1]), &v.0[1]);
+            assert_eq!(actual.0[i], expected);
+        }
+    }
+}
@@ -1,79 +1,569 @@
 //! Parameter sets for ML-DSA
+//!
+//! The parameters of each ML-DSA parameter set (FIPS 204 Section 4, Table 1) are expressed as
+//! `typenum` associated types on the [`ParameterSet`] trait, so that the sizes of keys and
+//! signatures can be computed at compile time.  The [`SigningKeyParams`],
+//! [`VerifyingKeyParams`], and [`SignatureParams`] sub-traits derive those sizes; they are
+//! implemented for every parameter set through blanket implementations.
 
-/// ML-DSA parameter set 44 (Security Category 2)
-pub mod ml_dsa_44 {
-    /// Ring dimension (256 for all ML-DSA variants)
-    pub const N: usize = 256;
-    /// Prime modulus Q = 2^23 - 2^13 + 1 = 8380417
-    pub const Q: u32 = 8380417;
-    /// Number of rows in the A matrix
-    pub const K: usize = 4;
-    /// Number of columns in the A matrix  
-    pub const L: usize = 4;
-    /// Private key range parameter
-    pub const ETA: u32 = 2;
-    /// Error size bound for y (Gamma1 = 2^17)
-    pub const GAMMA1: u32 = 131072; // 2^17
-    /// Low-order rounding range (Gamma2 = (Q-1)/88)
-    pub const GAMMA2: u32 = 95232; // (8380417-1)/88
-    /// Collision strength parameter (lambda/4 in bytes)
-    pub const LAMBDA: usize = 32;
-    /// Max number of true values in the hint
-    pub const OMEGA: usize = 80;
-    /// Number of nonzero values in the polynomial c
-    pub const TAU: usize = 39;
-    /// Derived parameter Beta = TAU * ETA
-    pub const BETA: u32 = 78; // 39 * 2
-}
-
-/// ML-DSA parameter set 65 (Security Category 3)
-pub mod ml_dsa_65 {
-    /// Ring dimension (256 for all ML-DSA variants)
-    pub const N: usize = 256;
-    /// Prime modulus Q = 2^23 - 2^13 + 1 = 8380417
-    pub const Q: u32 = 8380417;
-    /// Number of rows in the A matrix
-    pub const K: usize = 6;
-    /// Number of columns in the A matrix
-    pub const L: usize = 5;
-    /// Private key range parameter
-    pub const ETA: u32 = 4;
-    /// Error size bound for y (Gamma1 = 2^19)
-    pub const GAMMA1: u32 = 524288; // 2^19
-    /// Low-order rounding range (Gamma2 = (Q-1)/32)
-    pub const GAMMA2: u32 = 261888; // (8380417-1)/32
-    /// Collision strength parameter (lambda/4 in bytes)
-    pub const LAMBDA: usize = 48;
-    /// Max number of true values in the hint
-    pub const OMEGA: usize = 55;
-    /// Number of nonzero values in the polynomial c
-    pub const TAU: usize = 49;
-    /// Derived parameter Beta = TAU * ETA
-    pub const BETA: u32 = 196; // 49 * 4
-}
-
-/// ML-DSA parameter set 87 (Security Category 5)
-pub mod ml_dsa_87 {
-    /// Ring dimension (256 for all ML-DSA variants)
-    pub const N: usize = 256;
-    /// Prime modulus Q = 2^23 - 2^13 + 1 = 8380417
-    pub const Q: u32 = 8380417;
-    /// Number of rows in the A matrix
-    pub const K: usize = 8;
-    /// Number of columns in the A matrix
-    pub const L: usize = 7;
-    /// Private key range parameter
-    pub const ETA: u32 = 2;
-    /// Error size bound for y (Gamma1 = 2^19)
-    pub const GAMMA1: u32 = 524288; // 2^19
-    /// Low-order rounding range (Gamma2 = (Q-1)/32)
-    pub const GAMMA2: u32 = 261888; // (8380417-1)/32
-    /// Collision strength parameter (lambda/4 in bytes)
-    pub const LAMBDA: usize = 64;
-    /// Max number of true values in the hint
-    pub const OMEGA: usize = 75;
-    /// Number of nonzero values in the polynomial c
-    pub const TAU: usize = 60;
-    /// Derived parameter Beta = TAU * ETA
-    pub const BETA: u32 = 120; // 60 * 2
-}
\ No newline at end of file
+use core::fmt::Debug;
+use core::ops::{Add, Sub};
+use hybrid_array::{
+    Array,
+    typenum::{
+        Diff, Len, Length, Quot, Shleft, Sum, U1, U2, U4, U5, U6, U7, U8, U10, U13, U16, U17, U19,
+        U32, U39, U44, U48, U49, U55, U60, U64, U75, U80, U88, U128, U4096, Unsigned,
+    },
+};
+
+use crate::algebra::{QMinus1, Vector};
+use crate::encode::{BitPack, RangeEncodedVectorSize, RangeEncodingSize};
+use crate::hint::Hint;
+use crate::module_lattice::encode::{
+    ArraySize, Encode, EncodedVectorSize, EncodingSize, VectorEncodingSize,
+};
+use crate::util::{B32, B64};
+
+#[cfg(feature = "pkcs8")]
+use const_oid::{AssociatedOid, ObjectIdentifier, db::fips204};
+
+/// The values of the private key range parameter `eta` used by ML-DSA
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Eta {
+    /// `eta = 2` (ML-DSA-44 and ML-DSA-87)
+    Two,
+    /// `eta = 4` (ML-DSA-65)
+    Four,
+}
+
+/// A type-level integer that can be used as the private key range parameter `eta`
+pub trait SamplingSize: Unsigned {
+    /// The runtime value of `eta`
+    const ETA: Eta;
+}
+
+impl SamplingSize for U2 {
+    const ETA: Eta = Eta::Two;
+}
+
+impl SamplingSize for U4 {
+    const ETA: Eta = Eta::Four;
+}
+
+/// The upper end of the range of `t0` coefficients, `2^(d-1)`
+pub type T0Max = U4096;
+/// The magnitude of the lower end of the range of `t0` coefficients, `2^(d-1) - 1`
+pub type T0Min = Diff<T0Max, U1>;
+/// The number of bits used to encode each coefficient of `t0`, which is `d`
+pub type T0Bits = U13;
+
+/// An ML-DSA parameter set (FIPS 204 Table 1)
+pub trait ParameterSet: Clone + Debug + Default + PartialEq + 'static {
+    /// Number of rows in the `A` matrix
+    type K: ArraySize;
+    /// Number of columns in the `A` matrix
+    type L: ArraySize;
+    /// Private key range
+    type Eta: SamplingSize;
+    /// Coefficient range of `y`; always a power of two
+    type Gamma1: Unsigned + Len<Output: EncodingSize>;
+    /// Low-order rounding range
+    type Gamma2: Unsigned;
+    /// `2 * gamma2`, the modulus used by `Decompose`
+    type TwoGamma2: Unsigned;
+    /// The number of bits needed to encode a coefficient of `w1`
+    type W1Bits: EncodingSize;
+    /// Number of `±1` coefficients in the challenge polynomial `c`
+    type Tau: Unsigned;
+    /// Collision strength of `c̃`, expressed as the byte length `lambda / 4`
+    type Lambda: ArraySize;
+    /// Maximum number of ones in the hint
+    type Omega: ArraySize;
+
+    /// `beta = tau * eta`
+    const BETA: u32 = Self::Tau::U32 * Self::Eta::U32;
+}
+
+/// Encoded sizes for signing keys (FIPS 204 Algorithm 24 skEncode)
+pub trait SigningKeyParams: ParameterSet {
+    /// Size of the packed `s1`
+    type S1Size: ArraySize;
+    /// Size of the packed `s2`
+    type S2Size: ArraySize;
+    /// Size of the packed `t0`
+    type T0Size: ArraySize;
+    /// Size of the encoded signing key
+    type SigningKeySize: ArraySize;
+
+    /// Pack `s1` with coefficients in `[-eta, eta]`
+    fn encode_s1(s1: &Vector<Self::L>) -> EncodedS1<Self>;
+    /// Unpack `s1`, returning `None` if a coefficient lies outside `[-eta, eta]`
+    fn decode_s1(enc: &EncodedS1<Self>) -> Option<Vector<Self::L>>;
+
+    /// Pack `s2` with coefficients in `[-eta, eta]`
+    fn encode_s2(s2: &Vector<Self::K>) -> EncodedS2<Self>;
+    /// Unpack `s2`, returning `None` if a coefficient lies outside `[-eta, eta]`
+    fn decode_s2(enc: &EncodedS2<Self>) -> Option<Vector<Self::K>>;
+
+    /// Pack `t0` with coefficients in `[-(2^(d-1) - 1), 2^(d-1)]`
+    fn encode_t0(t0: &Vector<Self::K>) -> EncodedT0<Self>;
+    /// Unpack `t0`, returning `None` if a coefficient lies outside `[-(2^(d-1) - 1), 2^(d-1)]`
+    fn decode_t0(enc: &EncodedT0<Self>) -> Option<Vector<Self::K>>;
+
+    /// Concatenate the components of a signing key into its encoding
+    #[allow(non_snake_case)]
+    fn concat_sk(
+        rho: B32,
+        K: B32,
+        tr: B64,
+        s1: EncodedS1<Self>,
+        s2: EncodedS2<Self>,
+        t0: EncodedT0<Self>,
+    ) -> EncodedSigningKey<Self>;
+
+    /// Split an encoded signing key into its components
+    #[allow(clippy::type_complexity)]
+    fn split_sk(
+        enc: &EncodedSigningKey<Self>,
+    ) -> (
+        &B32,
+        &B32,
+        &B64,
+        &EncodedS1<Self>,
+        &EncodedS2<Self>,
+        &EncodedT0<Self>,
+    );
+}
+
+/// A packed `s1` for the parameter set `P`
+pub type EncodedS1<P> = Array<u8, <P as SigningKeyParams>::S1Size>;
+/// A packed `s2` for the parameter set `P`
+pub type EncodedS2<P> = Array<u8, <P as SigningKeyParams>::S2Size>;
+/// A packed `t0` for the parameter set `P`
+pub type EncodedT0<P> = Array<u8, <P as SigningKeyParams>::T0Size>;
+
+/// An encoded signing key for the parameter set `P`
+pub type EncodedSigningKey<P> = Array<u8, <P as SigningKeyParams>::SigningKeySize>;
+
+type EtaRange<P> = (<P as ParameterSet>::Eta, <P as ParameterSet>::Eta);
+type S1Size<P> = RangeEncodedVectorSize<
+    <P as ParameterSet>::Eta,
+    <P as ParameterSet>::Eta,
+    <P as ParameterSet>::L,
+>;
+type S2Size<P> = RangeEncodedVectorSize<
+    <P as ParameterSet>::Eta,
+    <P as ParameterSet>::Eta,
+    <P as ParameterSet>::K,
+>;
+type T0Size<P> = EncodedVectorSize<T0Bits, <P as ParameterSet>::K>;
+
+impl<P> SigningKeyParams for P
+where
+    P: ParameterSet,
+    EtaRange<P>: RangeEncodingSize,
+    <EtaRange<P> as RangeEncodingSize>::EncodingSize:
+        VectorEncodingSize<P::L> + VectorEncodingSize<P::K>,
+    T0Bits: VectorEncodingSize<P::K>,
+    U128: Add<S1Size<P>>,
+    Sum<U128, S1Size<P>>: ArraySize + Add<S2Size<P>> + Sub<U128, Output = S1Size<P>>,
+    Sum<Sum<U128, S1Size<P>>, S2Size<P>>:
+        ArraySize + Add<T0Size<P>> + Sub<Sum<U128, S1Size<P>>, Output = S2Size<P>>,
+    Sum<Sum<Sum<U128, S1Size<P>>, S2Size<P>>, T0Size<P>>:
+        ArraySize + Sub<Sum<Sum<U128, S1Size<P>>, S2Size<P>>, Output = T0Size<P>>,
+{
+    type S1Size = S1Size<P>;
+    type S2Size = S2Size<P>;
+    type T0Size = T0Size<P>;
+    type SigningKeySize = Sum<Sum<Sum<U128, S1Size<P>>, S2Size<P>>, T0Size<P>>;
+
+    fn encode_s1(s1: &Vector<P::L>) -> EncodedS1<Self> {
+        BitPack::<P::Eta, P::Eta>::pack(s1)
+    }
+
+    fn decode_s1(enc: &EncodedS1<Self>) -> Option<Vector<P::L>> {
+        BitPack::<P::Eta, P::Eta>::unpack(enc)
+    }
+
+    fn encode_s2(s2: &Vector<P::K>) -> EncodedS2<Self> {
+        BitPack::<P::Eta, P::Eta>::pack(s2)
+    }
+
+    fn decode_s2(enc: &EncodedS2<Self>) -> Option<Vector<P::K>> {
+        BitPack::<P::Eta, P::Eta>::unpack(enc)
+    }
+
+    fn encode_t0(t0: &Vector<P::K>) -> EncodedT0<Self> {
+        BitPack::<T0Min, T0Max>::pack(t0)
+    }
+
+    fn decode_t0(enc: &EncodedT0<Self>) -> Option<Vector<P::K>> {
+        BitPack::<T0Min, T0Max>::unpack(enc)
+    }
+
+    #[allow(non_snake_case)]
+    fn concat_sk(
+        rho: B32,
+        K: B32,
+        tr: B64,
+        s1: EncodedS1<Self>,
+        s2: EncodedS2<Self>,
+        t0: EncodedT0<Self>,
+    ) -> EncodedSigningKey<Self> {
+        rho.concat(K).concat(tr).concat(s1).concat(s2).concat(t0)
+    }
+
+    #[allow(non_snake_case, clippy::type_complexity)]
+    fn split_sk(
+        enc: &EncodedSigningKey<Self>,
+    ) -> (
+        &B32,
+        &B32,
+        &B64,
+        &EncodedS1<Self>,
+        &EncodedS2<Self>,
+        &EncodedT0<Self>,
+    ) {
+        let (enc, t0) = enc.split_ref();
+        let (enc, s2) = enc.split_ref();
+        let (enc, s1) = enc.split_ref::<U128>();
+        let (enc, tr) = enc.split_ref::<U64>();
+        let (rho, K) = enc.split_ref();
+        (rho, K, tr, s1, s2, t0)
+    }
+}
+
+/// The number of bits used to encode each coefficient of `t1`, `bitlen(q - 1) - d`
+pub type T1Bits = U10;
+
+/// Encoded sizes for verifying keys (FIPS 204 Algorithm 22 pkEncode)
+pub trait VerifyingKeyParams: ParameterSet {
+    /// Size of the packed `t1`
+    type T1Size: ArraySize;
+    /// Size of the encoded verifying key
+    type VerifyingKeySize: ArraySize;
+
+    /// Pack `t1` with `T1Bits` bits per coefficient (SimpleBitPack)
+    fn encode_t1(t1: &Vector<Self::K>) -> EncodedT1<Self>;
+
+    /// Unpack `t1`; every encoding is valid
+    fn decode_t1(enc: &EncodedT1<Self>) -> Vector<Self::K>;
+
+    /// Concatenate `rho` and the packed `t1` into an encoded verifying key
+    fn concat_vk(rho: B32, t1: EncodedT1<Self>) -> EncodedVerifyingKey<Self>;
+
+    /// Split an encoded verifying key into `rho` and the packed `t1`
+    fn split_vk(enc: &EncodedVerifyingKey<Self>) -> (&B32, &EncodedT1<Self>);
+}
+
+/// A packed `t1` for the parameter set `P`
+pub type EncodedT1<P> = Array<u8, <P as VerifyingKeyParams>::T1Size>;
+
+/// An encoded verifying key for the parameter set `P`
+pub type EncodedVerifyingKey<P> = Array<u8, <P as VerifyingKeyParams>::VerifyingKeySize>;
+
+type T1Size<P> = EncodedVectorSize<T1Bits, <P as ParameterSet>::K>;
+
+impl<P> VerifyingKeyParams for P
+where
+    P: ParameterSet,
+    T1Bits: VectorEncodingSize<P::K>,
+    U32: Add<T1Size<P>>,
+    Sum<U32, T1Size<P>>: ArraySize + Sub<U32, Output = T1Size<P>>,
+{
+    type T1Size = T1Size<P>;
+    type VerifyingKeySize = Sum<U32, T1Size<P>>;
+
+    fn encode_t1(t1: &Vector<P::K>) -> EncodedT1<Self> {
+        Encode::<T1Bits>::encode(t1)
+    }
+
+    fn decode_t1(enc: &EncodedT1<Self>) -> Vector<P::K> {
+        Encode::<T1Bits>::decode(enc)
+    }
+
+    fn concat_vk(rho: B32, t1: EncodedT1<Self>) -> EncodedVerifyingKey<Self> {
+        rho.concat(t1)
+    }
+
+    fn split_vk(enc: &EncodedVerifyingKey<Self>) -> (&B32, &EncodedT1<Self>) {
+        enc.split_ref()
+    }
+}
+
+/// Encoded sizes and derived bounds for signatures (FIPS 204 Algorithm 26 sigEncode)
+pub trait SignatureParams: ParameterSet {
+    /// Size of the encoded `w1` that is hashed into the commitment
+    type W1Size: ArraySize;
+    /// Size of the packed `z`
+    type ZSize: ArraySize;
+    /// Size of the packed hint
+    type HintSize: ArraySize;
+    /// Size of the encoded signature
+    type SignatureSize: ArraySize;
+
+    /// `gamma1 - beta`, the exclusive bound on `||z||∞`
+    const GAMMA1_MINUS_BETA: u32;
+    /// `gamma2 - beta`, the exclusive bound on `||r0||∞`
+    const GAMMA2_MINUS_BETA: u32;
+
+    /// Pack `w1` for hashing into the commitment (Algorithm 28 w1Encode)
+    fn encode_w1(w1: &Vector<Self::K>) -> EncodedW1<Self>;
+
+    /// Pack `z` with coefficients in `[-(gamma1 - 1), gamma1]`
+    fn encode_z(z: &Vector<Self::L>) -> EncodedZ<Self>;
+
+    /// Pack a hint with at most `omega` set bits
+    fn encode_hint(h: &Hint<Self::K>) -> EncodedSignatureHint<Self>;
+
+    /// Concatenate `c̃` and the packed `z` and hint into an encoded signature
+    fn concat_sig(
+        c_tilde: EncodedCTilde<Self>,
+        z: EncodedZ<Self>,
+        h: EncodedSignatureHint<Self>,
+    ) -> EncodedSignature<Self>;
+
+    /// Unpack `z`, returning `None` if a coefficient lies outside `[-(gamma1 - 1), gamma1]`
+    ///
+    /// The caller is still responsible for checking `||z||∞ < gamma1 - beta`.
+    fn decode_z(z: &EncodedZ<Self>) -> Option<Vector<Self::L>>;
+
+    /// Unpack a hint, returning `None` if the encoding is malformed
+    fn decode_hint(h: &EncodedSignatureHint<Self>) -> Option<Hint<Self::K>>;
+
+    /// Split an encoded signature into `c̃` and the packed `z` and hint
+    fn split_sig(
+        enc: &EncodedSignature<Self>,
+    ) -> (
+        &EncodedCTilde<Self>,
+        &EncodedZ<Self>,
+        &EncodedSignatureHint<Self>,
+    );
+}
+
+/// An encoded `w1` for the parameter set `P`
+pub type EncodedW1<P> = Array<u8, <P as SignatureParams>::W1Size>;
+/// The commitment hash `c̃` for the parameter set `P`
+pub type EncodedCTilde<P> = Array<u8, <P as ParameterSet>::Lambda>;
+/// A packed `z` for the parameter set `P`
+pub type EncodedZ<P> = Array<u8, <P as SignatureParams>::ZSize>;
+/// A packed hint for the parameter set `P`
+pub type EncodedSignatureHint<P> = Array<u8, <P as SignatureParams>::HintSize>;
+
+/// An encoded signature for the parameter set `P`
+pub type EncodedSignature<P> = Array<u8, <P as SignatureParams>::SignatureSize>;
+
+type Gamma1Range<P> = (
+    Diff<<P as ParameterSet>::Gamma1, U1>,
+    <P as ParameterSet>::Gamma1,
+);
+type W1Size<P> = EncodedVectorSize<<P as ParameterSet>::W1Bits, <P as ParameterSet>::K>;
+type ZSize<P> = RangeEncodedVectorSize<
+    Diff<<P as ParameterSet>::Gamma1, U1>,
+    <P as ParameterSet>::Gamma1,
+    <P as ParameterSet>::L,
+>;
+type HintSize<P> = Sum<<P as ParameterSet>::Omega, <P as ParameterSet>::K>;
+
+impl<P> SignatureParams for P
+where
+    P: ParameterSet,
+    P::W1Bits: VectorEncodingSize<P::K>,
+    P::Gamma1: Sub<U1>,
+    Gamma1Range<P>: RangeEncodingSize,
+    <Gamma1Range<P> as RangeEncodingSize>::EncodingSize: VectorEncodingSize<P::L>,
+    P::Omega: Add<P::K>,
+    HintSize<P>: ArraySize,
+    P::Lambda: Add<ZSize<P>>,
+    Sum<P::Lambda, ZSize<P>>: ArraySize + Add<HintSize<P>> + Sub<P::Lambda, Output = ZSize<P>>,
+    Sum<Sum<P::Lambda, ZSize<P>>, HintSize<P>>:
+        ArraySize + Sub<Sum<P::Lambda, ZSize<P>>, Output = HintSize<P>>,
+{
+    type W1Size = W1Size<P>;
+    type ZSize = ZSize<P>;
+    type HintSize = HintSize<P>;
+    type SignatureSize = Sum<Sum<P::Lambda, ZSize<P>>, HintSize<P>>;
+
+    const GAMMA1_MINUS_BETA: u32 = P::Gamma1::U32 - P::BETA;
+    const GAMMA2_MINUS_BETA: u32 = P::Gamma2::U32 - P::BETA;
+
+    fn encode_w1(w1: &Vector<P::K>) -> EncodedW1<Self> {
+        Encode::<P::W1Bits>::encode(w1)
+    }
+
+    fn encode_z(z: &Vector<P::L>) -> EncodedZ<Self> {
+        BitPack::<Diff<P::Gamma1, U1>, P::Gamma1>::pack(z)
+    }
+
+    fn encode_hint(h: &Hint<P::K>) -> EncodedSignatureHint<Self> {
+        h.bit_pack::<P::Omega>()
+    }
+
+    fn concat_sig(
+        c_tilde: EncodedCTilde<Self>,
+        z: EncodedZ<Self>,
+        h: EncodedSignatureHint<Self>,
+    ) -> EncodedSignature<Self> {
+        c_tilde.concat(z).concat(h)
+    }
+
+    fn decode_z(z: &EncodedZ<Self>) -> Option<Vector<P::L>> {
+        BitPack::<Diff<P::Gamma1, U1>, P::Gamma1>::unpack(z)
+    }
+
+    fn decode_hint(h: &EncodedSignatureHint<Self>) -> Option<Hint<P::K>> {
+        Hint::bit_unpack::<P::Omega>(h)
+    }
+
+    fn split_sig(
+        enc: &EncodedSignature<Self>,
+    ) -> (
+        &EncodedCTilde<Self>,
+        &EncodedZ<Self>,
+        &EncodedSignatureHint<Self>,
+    ) {
+        let (c_tilde_z, h) = enc.split_ref();
+        let (c_tilde, z) = c_tilde_z.split_ref();
+        (c_tilde, z, h)
+    }
+}
+
+/// A complete ML-DSA parameter set, with all the derived sizes needed for keys and signatures
+pub trait MlDsaParams: SigningKeyParams + VerifyingKeyParams + SignatureParams {}
+
+impl<P> MlDsaParams for P where P: SigningKeyParams + VerifyingKeyParams + SignatureParams {}
+
+/// `bitlen((q - 1) / (2 * gamma2) - 1)`, for `gamma2 = (q - 1) / M`
+type W1BitsFor<M> = Length<Diff<Quot<M, U2>, U1>>;
+
+/// ML-DSA-44 (Security Category 2)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MlDsa44;
+
+impl ParameterSet for MlDsa44 {
+    type K = U4;
+    type L = U4;
+    type Eta = U2;
+    type Gamma1 = Shleft<U1, U17>;
+    type Gamma2 = Quot<QMinus1, U88>;
+    type TwoGamma2 = Quot<QMinus1, U44>;
+    type W1Bits = W1BitsFor<U88>;
+    type Tau = U39;
+    type Lambda = U32;
+    type Omega = U80;
+}
+
+#[cfg(feature = "pkcs8")]
+impl AssociatedOid for MlDsa44 {
+    const OID: ObjectIdentifier = fips204::ID_ML_DSA_44;
+}
+
+/// ML-DSA-65 (Security Category 3)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MlDsa65;
+
+impl ParameterSet for MlDsa65 {
+    type K = U6;
+    type L = U5;
+    type Eta = U4;
+    type Gamma1 = Shleft<U1, U19>;
+    type Gamma2 = Quot<QMinus1, U32>;
+    type TwoGamma2 = Quot<QMinus1, U16>;
+    type W1Bits = W1BitsFor<U32>;
+    type Tau = U49;
+    type Lambda = U48;
+    type Omega = U55;
+}
+
+#[cfg(feature = "pkcs8")]
+impl AssociatedOid for MlDsa65 {
+    const OID: ObjectIdentifier = fips204::ID_ML_DSA_65;
+}
+
+/// ML-DSA-87 (Security Category 5)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MlDsa87;
+
+impl ParameterSet for MlDsa87 {
+    type K = U8;
+    type L = U7;
+    type Eta = U2;
+    type Gamma1 = Shleft<U1, U19>;
+    type Gamma2 = Quot<QMinus1, U32>;
+    type TwoGamma2 = Quot<QMinus1, U16>;
+    type W1Bits = W1BitsFor<U32>;
+    type Tau = U60;
+    type Lambda = U64;
+    type Omega = U75;
+}
+
+#[cfg(feature = "pkcs8")]
+impl AssociatedOid for MlDsa87 {
+    const OID: ObjectIdentifier = fips204::ID_ML_DSA_87;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check<P>(params: [u32; 9], sizes: [usize; 3])
+    where
+        P: MlDsaParams,
+    {
+        let [k, l, eta, gamma1, gamma2, tau, beta, lambda, omega] = params;
+        assert_eq!(P::K::U32, k);
+        assert_eq!(P::L::U32, l);
+        assert_eq!(P::Eta::U32, eta);
+        assert_eq!(P::Gamma1::U32, gamma1);
+        assert_eq!(P::Gamma2::U32, gamma2);
+        assert_eq!(P::TwoGamma2::U32, 2 * gamma2);
+        assert_eq!(P::Tau::U32, tau);
+        assert_eq!(P::BETA, beta);
+        assert_eq!(P::Lambda::U32, lambda);
+        assert_eq!(P::Omega::U32, omega);
+
+        let [pk, sk, sig] = sizes;
+        assert_eq!(P::VerifyingKeySize::USIZE, pk);
+        assert_eq!(P::SigningKeySize::USIZE, sk);
+        assert_eq!(P::SignatureSize::USIZE, sig);
+
+        assert_eq!(P::GAMMA1_MINUS_BETA, gamma1 - beta);
+        assert_eq!(P::GAMMA2_MINUS_BETA, gamma2 - beta);
+    }
+
+    #[test]
+    fn t0_range() {
+        use crate::encode::RangeEncodingBits;
+        assert_eq!(RangeEncodingBits::<T0Min, T0Max>::USIZE, T0Bits::USIZE);
+    }
+
+    #[test]
+    fn ml_dsa_44() {
+        check::<MlDsa44>(
+            [4, 4, 2, 1 << 17, 95232, 39, 78, 32, 80],
+            [1312, 2560, 2420],
+        );
+        assert_eq!(<MlDsa44 as ParameterSet>::W1Bits::USIZE, 6);
+        assert_eq!(<MlDsa44 as SignatureParams>::W1Size::USIZE, 768);
+    }
+
+    #[test]
+    fn ml_dsa_65() {
+        check::<MlDsa65>(
+            [6, 5, 4, 1 << 19, 261888, 49, 196, 48, 55],
+            [1952, 4032, 3309],
+        );
+        assert_eq!(<MlDsa65 as ParameterSet>::W1Bits::USIZE, 4);
+        assert_eq!(<MlDsa65 as SignatureParams>::W1Size::USIZE, 768);
+    }
+
+    #[test]
+    fn ml_dsa_87() {
+        check::<MlDsa87>(
+            [8, 7, 2, 1 << 19, 261888, 60, 120, 64, 75],
+            [2592, 4896, 4627],
+        );
+        assert_eq!(<MlDsa87 as ParameterSet>::W1Bits::USIZE, 4);
+        assert_eq!(<MlDsa87 as SignatureParams>::W1Size::USIZE, 1024);
+    }
+}
@@ -0,0 +1,524 @@
+//! HashML-DSA: signing and verification of pre-hashed messages
+//!
+//! HashML-DSA (FIPS 204 Section 5.4, Algorithms 4 and 5) signs a digest `PH(M)` of the message
+//! instead of the message itself.  The DER encoding of the hash function's object identifier is
+//! bound into the signed string `M' = 1 || |ctx| || ctx || OID || PH(M)`, so a signature over a
+//! digest can never be confused with a pure ML-DSA signature or with a digest from a different
+//! hash function.
+//!
+//! The caller computes the digest; this module only checks that its length matches the chosen
+//! hash function.  Alternatively, any [`Digest`] with an [`AssociatedOid`] naming one of the
+//! approved hash functions can be used through the `signature` crate's [`DigestSigner`] and
+//! [`DigestVerifier`] traits.
+
+use const_oid::{
+    ObjectIdentifier,
+    db::{fips202, rfc5912},
+};
+use signature::{
+    DigestSigner, DigestVerifier, Error,
+    digest::{Digest, const_oid::AssociatedOid},
+};
+
+#[cfg(feature = "rand_core")]
+use {
+    rand_core::{CryptoRng, TryCryptoRng},
+    signature::RandomizedDigestSigner,
+};
+
+use crate::util::{B32, B64};
+use crate::{MlDsaError, MlDsaParams, Signature, SigningKey, VerifyingKey, ensure_operational};
+
+/// The tag of an ASN.1 OBJECT IDENTIFIER
+const OID_TAG: u8 = 0x06;
+
+/// A hash function that may be used to pre-hash messages for HashML-DSA
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PreHash {
+    /// SHA-256
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+    /// SHA3-256
+    Sha3_256,
+    /// SHA3-384
+    Sha3_384,
+    /// SHA3-512
+    Sha3_512,
+    /// SHAKE128 with 256 bits of output
+    Shake128,
+    /// SHAKE256 with 512 bits of output
+    Shake256,
+}
+
+impl PreHash {
+    /// All the hash functions approved for HashML-DSA
+    pub const ALL: [Self; 8] = [
+        Self::Sha256,
+        Self::Sha384,
+        Self::Sha512,
+        Self::Sha3_256,
+        Self::Sha3_384,
+        Self::Sha3_512,
+        Self::Shake128,
+        Self::Shake256,
+    ];
+
+    /// The hash function with the object identifier `oid`, if it is one of the approved ones
+    pub fn from_oid(oid: ObjectIdentifier) -> Option<Self> {
+        Self::ALL.into_iter().find(|ph| ph.oid() == oid)
+    }
+
+    /// The object identifier of the hash function
+    pub fn oid(&self) -> ObjectIdentifier {
+        match self {
+            Self::Sha256 => rfc5912::ID_SHA_256,
+            Self::Sha384 => rfc5912::ID_SHA_384,
+            Self::Sha512 => rfc5912::ID_SHA_512,
+            Self::Sha3_256 => fips202::ID_SHA_3_256,
+            Self::Sha3_384 => fips202::ID_SHA_3_384,
+            Self::Sha3_512 => fips202::ID_SHA_3_512,
+            Self::Shake128 => fips202::ID_SHAKE_128,
+            Self::Shake256 => fips202::ID_SHAKE_256,
+        }
+    }
+
+    /// The length in bytes of the digests that are signed
+    pub fn digest_len(&self) -> usize {
+        match self {
+            Self::Sha256 | Self::Sha3_256 | Self::Shake128 => 32,
+            Self::Sha384 | Self::Sha3_384 => 48,
+            Self::Sha512 | Self::Sha3_512 | Self::Shake256 => 64,
+        }
+    }
+}
+
+// Assemble the pieces of M' = 1 || |ctx| || ctx || OID || PH(M) and pass them to `f`.  The OID
+// is written in full DER form, tag and length included.
+fn with_message_prime<T>(
+    ph: PreHash,
+    digest: &[u8],
+    ctx: &[u8],
+    f: impl FnOnce(&[&[u8]]) -> T,
+) -> Result<T, Error> {
+    ensure_operational()?;
+    let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+    if digest.len() != ph.digest_len() {
+        return Err(MlDsaError::InvalidLength.into());
+    }
+
+    let oid = ph.oid();
+    let oid_len =
+        u8::try_from(oid.as_bytes().len()).map_err(|_| MlDsaError::UnsupportedAlgorithm)?;
+
+    Ok(f(&[
+        &[1],
+        &[ctx_len],
+        ctx,
+        &[OID_TAG, oid_len],
+        oid.as_bytes(),
+        digest,
+    ]))
+}
+
+impl<P: MlDsaParams> SigningKey<P> {
+    /// Sign the digest `digest = PH(M)` with the context string `ctx`, using fresh randomness
+    /// from `rng` (Algorithm 4 HashML-DSA.Sign, hedged variant)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes or `digest` does not have the output
+    /// length of `ph`.
+    #[cfg(feature = "rand_core")]
+    pub fn sign_prehash_randomized<R: CryptoRng + ?Sized>(
+        &self,
+        ph: PreHash,
+        digest: &[u8],
+        ctx: &[u8],
+        rng: &mut R,
+    ) -> Result<Signature<P>, Error> {
+        // rnd is drawn only once the context and digest are known to be valid
+        with_message_prime(ph, digest, ctx, |mp| {
+            let mut rnd = B32::default();
+            rng.fill_bytes(&mut rnd);
+            self.try_sign_internal(mp, &rnd)
+        })?
+    }
+
+    /// Sign the digest `digest = PH(M)` with the context string `ctx`, deterministically
+    /// (Algorithm 4 HashML-DSA.Sign, deterministic variant with `rnd = 0^32`)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes or `digest` does not have the output
+    /// length of `ph`.
+    pub fn sign_prehash_deterministic(
+        &self,
+        ph: PreHash,
+        digest: &[u8],
+        ctx: &[u8],
+    ) -> Result<Signature<P>, Error> {
+        with_message_prime(ph, digest, ctx, |mp| {
+            self.try_sign_internal(mp, &B32::default())
+        })?
+    }
+}
+
+impl<P: MlDsaParams> VerifyingKey<P> {
+    /// Compute the message representative `mu` for HashML-DSA, to be signed with
+    /// [`SigningKey::try_sign_mu`]
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes or `digest` does not have the output
+    /// length of `ph`.
+    pub fn compute_prehash_mu(&self, ph: PreHash, digest: &[u8], ctx: &[u8]) -> Result<B64, Error> {
+        with_message_prime(ph, digest, ctx, |mp| self.compute_mu_internal(mp))
+    }
+
+    /// Verify a signature on the digest `digest = PH(M)` with the context string `ctx`
+    /// (Algorithm 5 HashML-DSA.Verify)
+    ///
+    /// Returns an error if the signature is invalid, `ctx` is longer than 255 bytes, or `digest`
+    /// does not have the output length of `ph`.
+    pub fn verify_prehash(
+        &self,
+        ph: PreHash,
+        digest: &[u8],
+        ctx: &[u8],
+        sigma: &Signature<P>,
+    ) -> Result<(), Error> {
+        let valid = with_message_prime(ph, digest, ctx, |mp| self.verify_internal(mp, sigma))?;
+        valid
+            .then_some(())
+            .ok_or_else(|| MlDsaError::InvalidSignature.into())
+    }
+}
+
+/// Deterministic HashML-DSA signing of a digest from an approved hash function, with an empty
+/// context string
+impl<P, D> DigestSigner<D, Signature<P>> for SigningKey<P>
+where
+    P: MlDsaParams,
+    D: Digest + AssociatedOid,
+{
+    fn try_sign_digest(&self, digest: D) -> Result<Signature<P>, Error> {
+        let ph = PreHash::from_oid(D::OID).ok_or(MlDsaError::UnsupportedAlgorithm)?;
+        self.sign_prehash_deterministic(ph, &digest.finalize(), &[])
+    }
+}
+
+/// Hedged HashML-DSA signing of a digest from an approved hash function, with an empty context
+/// string
+#[cfg(feature = "rand_core")]
+impl<P, D> RandomizedDigestSigner<D, Signature<P>> for SigningKey<P>
+where
+    P: MlDsaParams,
+    D: Digest + AssociatedOid,
+{
+    fn try_sign_digest_with_rng<R: TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        digest: D,
+    ) -> Result<Signature<P>, Error> {
+        let ph = PreHash::from_oid(D::OID).ok_or(MlDsaError::UnsupportedAlgorithm)?;
+
+        with_message_prime(ph, &digest.finalize(), &[], |mp| {
+            let mut rnd = B32::default();
+            rng.try_fill_bytes(&mut rnd)
+                .map_err(|_| MlDsaError::RngFailure)?;
+            self.try_sign_internal(mp, &rnd)
+        })?
+    }
+}
+
+/// HashML-DSA verification of a digest from an approved hash function, with an empty context
+/// string
+impl<P, D> DigestVerifier<D, Signature<P>> for VerifyingKey<P>
+where
+    P: MlDsaParams,
+    D: Digest + AssociatedOid,
+{
+    fn verify_digest(&self, digest: D, signature: &Signature<P>) -> Result<(), Error> {
+        let ph = PreHash::from_oid(D::OID).ok_or(MlDsaError::UnsupportedAlgorithm)?;
+        self.verify_prehash(ph, &digest.finalize(), &[], signature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyGen, MlDsa44, MlDsa65};
+    use hex_literal::hex;
+    use sha3::digest::{ExtendableOutput, Update};
+    use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake256};
+
+    const ALL: [PreHash; 8] = PreHash::ALL;
+
+    #[test]
+    fn oids() {
+        // The DER encodings listed in FIPS 204 Section 5.4.1 all share the NIST hash arc
+        // 2.16.840.1.101.3.4.2 and differ in the final byte
+        let expected = [0x01, 0x02, 0x03, 0x08, 0x09, 0x0a, 0x0b, 0x0c];
+        for (ph, last) in ALL.iter().zip(expected) {
+            let mut der = vec![OID_TAG, 9];
+            der.extend_from_slice(ph.oid().as_bytes());
+            assert_eq!(der[..10], hex!("06 09 60 86 48 01 65 03 04 02"));
+            assert_eq!(der[10], last);
+        }
+    }
+
+    #[test]
+    fn message_prime() {
+        let digest = Sha3_256::digest(b"message");
+        let mp = with_message_prime(PreHash::Sha3_256, &digest, b"ctx", |mp| mp.concat()).unwrap();
+
+        let mut expected = vec![1, 3];
+        expected.extend_from_slice(b"ctx");
+        expected.extend_from_slice(&hex!("06 09 60 86 48 01 65 03 04 02 08"));
+        expected.extend_from_slice(&digest);
+        assert_eq!(mp, expected);
+    }
+
+    #[test]
+    fn sign_verify() {
+        let kp = MlDsa44::key_gen_internal(&B32::from([1; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        for ph in ALL {
+            let digest = vec![0xa5; ph.digest_len()];
+            let sig = sk.sign_prehash_deterministic(ph, &digest, b"ctx").unwrap();
+            assert!(vk.verify_prehash(ph, &digest, b"ctx", &sig).is_ok());
+
+            // The digest, the context and the hash function are all bound into the signature
+            let mut other = digest.clone();
+            other[0] ^= 1;
+            assert!(vk.verify_prehash(ph, &other, b"ctx", &sig).is_err());
+            assert!(vk.verify_prehash(ph, &digest, b"", &sig).is_err());
+            for other_ph in ALL
+                .iter()
+                .filter(|&&p| p != ph && p.digest_len() == ph.digest_len())
+            {
+                assert!(vk.verify_prehash(*other_ph, &digest, b"ctx", &sig).is_err());
+            }
+
+            // The message representative can be computed separately
+            let mu = vk.compute_prehash_mu(ph, &digest, b"ctx").unwrap();
+            assert_eq!(sk.sign_mu_deterministic(&mu).unwrap(), sig);
+
+            // A HashML-DSA signature is not a pure ML-DSA signature on the digest
+            assert!(vk.verify_with_context(&digest, b"ctx", &sig).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        let kp = MlDsa65::key_gen_internal(&B32::from([2; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+        let digest = [0u8; 64];
+
+        // Digest length must match the hash function
+        assert!(
+            sk.sign_prehash_deterministic(PreHash::Sha256, &digest, &[])
+                .is_err()
+        );
+        assert!(
+            sk.sign_prehash_deterministic(PreHash::Shake256, &digest[..32], &[])
+                .is_err()
+        );
+
+        // Context strings are limited to 255 bytes
+        let sig = sk
+            .sign_prehash_deterministic(PreHash::Sha512, &digest, &[0; 255])
+            .unwrap();
+        assert!(
+            vk.verify_prehash(PreHash::Sha512, &digest, &[0; 255], &sig)
+                .is_ok()
+        );
+        assert!(
+            sk.sign_prehash_deterministic(PreHash::Sha512, &digest, &[0; 256])
+                .is_err()
+        );
+        assert!(
+            vk.verify_prehash(PreHash::Sha512, &digest, &[0; 256], &sig)
+                .is_err()
+        );
+        assert!(
+            vk.verify_prehash(PreHash::Sha256, &digest[..32], &[0; 255], &sig)
+                .is_err()
+        );
+    }
+
+    const KAT_MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    // Deterministic HashML-DSA-44 signatures on `KAT_MSG` with the context `01020304`, under
+    // the key derived from the seed 00 01 .. 1f, given as SHA3-256 digests.  OpenSSL 3.5 has
+    // no HashML-DSA mode, so M' was assembled separately and signed with OpenSSL's raw
+    // ML-DSA.Sign_internal.  The digests PH(M) are those of Python's hashlib.
+    fn sign_kat(ph: PreHash, digest: &[u8], expected: [u8; 32]) {
+        let seed = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+        let kp = MlDsa44::key_gen_internal(&B32::from(seed)).unwrap();
+        let ctx = [1, 2, 3, 4];
+
+        let sig = kp
+            .signing_key()
+            .sign_prehash_deterministic(ph, digest, &ctx)
+            .unwrap();
+        assert_eq!(Sha3_256::digest(sig.encode()).as_slice(), expected);
+        assert!(
+            kp.verifying_key()
+                .verify_prehash(ph, digest, &ctx, &sig)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn sign_kat_sha2() {
+        let digest = hex!("d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592");
+        assert_eq!(digest.len(), PreHash::Sha256.digest_len());
+        sign_kat(
+            PreHash::Sha256,
+            &digest,
+            hex!("9027a8db348c656e4b5fce908c463bff2d08e087595f36923d05a3ff8899a28a"),
+        );
+    }
+
+    #[test]
+    fn sign_kat_sha3() {
+        let digest = Sha3_384::digest(KAT_MSG);
+        assert_eq!(
+            digest.as_slice(),
+            hex!(
+                "7063465e08a93bce31cd89d2e3ca8f602498696e253592ed26f07bf7e703cf32"
+                "8581e1471a7ba7ab119b1a9ebdf8be41"
+            )
+        );
+        sign_kat(
+            PreHash::Sha3_384,
+            &digest,
+            hex!("9014428905791c866fd02ef7593015816f92dccb215a67ccffbccfda2fe032d0"),
+        );
+    }
+
+    #[test]
+    fn sign_kat_shake() {
+        let mut digest = [0u8; 64];
+        let mut xof = Shake256::default();
+        xof.update(KAT_MSG);
+        xof.finalize_xof_into(&mut digest);
+        assert_eq!(
+            digest,
+            hex!(
+                "2f671343d9b2e1604dc9dcf0753e5fe15c7c64a0d283cbbf722d411a0e36f6ca"
+                "1d01d1369a23539cd80f7c054b6e5daf9c962cad5b8ed5bd11998b40d5734442"
+            )
+        );
+        sign_kat(
+            PreHash::Shake256,
+            &digest,
+            hex!("3369ff062a2c675a161dbd1e712b7c65ee43470f4107973cee94ecf68168e113"),
+        );
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn sign_randomized() {
+        let mut rng = rand::rng();
+        let kp = MlDsa44::key_gen(&mut rng).unwrap();
+        let digest = Sha3_256::digest(b"message");
+
+        let sig = kp
+            .signing_key()
+            .sign_prehash_randomized(PreHash::Sha3_256, &digest, &[], &mut rng)
+            .unwrap();
+        assert!(
+            kp.verifying_key()
+                .verify_prehash(PreHash::Sha3_256, &digest, &[], &sig)
+                .is_ok()
+        );
+
+        // Bad inputs are rejected before any randomness is drawn, as in `sign_randomized`
+        struct UnusedRng;
+
+        impl rand_core::RngCore for UnusedRng {
+            fn next_u32(&mut self) -> u32 {
+                unreachable!("randomness drawn for a bad input")
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                unreachable!("randomness drawn for a bad input")
+            }
+
+            fn fill_bytes(&mut self, _: &mut [u8]) {
+                unreachable!("randomness drawn for a bad input")
+            }
+        }
+
+        impl CryptoRng for UnusedRng {}
+
+        let sk = kp.signing_key();
+        assert!(
+            sk.sign_prehash_randomized(PreHash::Sha3_256, &digest, &[0; 256], &mut UnusedRng)
+                .is_err()
+        );
+        assert!(
+            sk.sign_prehash_randomized(PreHash::Sha512, &digest, &[], &mut UnusedRng)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn from_oid() {
+        for ph in ALL {
+            assert_eq!(PreHash::from_oid(ph.oid()), Some(ph));
+        }
+        assert_eq!(PreHash::from_oid(Sha3_256::OID), Some(PreHash::Sha3_256));
+        assert_eq!(PreHash::from_oid(fips202::ID_SHA_3_224), None);
+    }
+
+    #[test]
+    fn digest_traits() {
+        let kp = MlDsa44::key_gen_internal(&B32::from([5; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        let sig = sk.sign_digest(Sha3_512::new_with_prefix(b"message"));
+        let expected = sk
+            .sign_prehash_deterministic(PreHash::Sha3_512, &Sha3_512::digest(b"message"), &[])
+            .unwrap();
+        assert_eq!(sig, expected);
+
+        assert!(
+            vk.verify_digest(Sha3_512::new_with_prefix(b"message"), &sig)
+                .is_ok()
+        );
+        assert!(
+            vk.verify_digest(Sha3_512::new_with_prefix(b"other"), &sig)
+                .is_err()
+        );
+        assert!(
+            vk.verify_digest(Sha3_256::new_with_prefix(b"message"), &sig)
+                .is_err()
+        );
+
+        #[cfg(feature = "rand_core")]
+        {
+            let sig = sk.sign_digest_with_rng(&mut rand::rng(), Sha3_256::new_with_prefix(b"m"));
+            assert!(
+                vk.verify_digest(Sha3_256::new_with_prefix(b"m"), &sig)
+                    .is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn digest_traits_reject_unapproved_hashes() {
+        // SHA3-224 has an OID but is not approved for HashML-DSA
+        let kp = MlDsa44::key_gen_internal(&B32::from([5; 32])).unwrap();
+        let result: Result<Signature<MlDsa44>, _> =
+            kp.signing_key().try_sign_digest(Sha3_224::new());
+        assert!(result.is_err());
+
+        let sig = kp.signing_key().sign_digest(Sha3_256::new());
+        assert!(
+            kp.verifying_key()
+                .verify_digest(Sha3_224::new(), &sig)
+                .is_err()
+        );
+    }
+}
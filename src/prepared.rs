@@ -0,0 +1,506 @@
+//! Keys with their NTT-domain values precomputed
+//!
+//! Every signature needs the matrix `Â = ExpandA(rho)` and the secret vectors in the NTT domain.
+//! Deriving them costs more than a typical pass through the rejection sampling loop, so a
+//! signer that uses the same key many times can do that work once, by preparing the key.  A
+//! [`SigningKey`] prepares itself on its first signature and keeps the prepared key.
+//! Likewise, every verification needs `Â` and `NTT(t1 * 2^d)`, which a prepared verifying key
+//! computes once.  Prepared keys produce exactly the same signatures and verification results as
+//! the keys they were prepared from.
+//!
+//! With the `verify-after-sign` feature, a prepared signing key also derives `NTT(t1 * 2^d)`
+//! and checks every signature it produces against it before releasing it.  A fault injected
+//! into the computation of the response `z` can leak the signing key, and a faulty signature
+//! almost never verifies, so this turns such a fault into an error.
+
+use core::fmt;
+use hybrid_array::{ArraySize, typenum::Unsigned};
+use signature::{Error, Signer, Verifier};
+
+#[cfg(feature = "rand_core")]
+use {
+    rand_core::{CryptoRng, TryCryptoRng},
+    signature::RandomizedSigner,
+};
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::algebra::{AlgebraExt, D, Elem, NttMatrix, NttVector, Vector};
+#[cfg(feature = "verify-after-sign")]
+use crate::compute_t;
+use crate::crypto::H;
+use crate::hint::Hint;
+use crate::ntt::{Ntt, NttInverse};
+use crate::param::EncodedCTilde;
+use crate::sampling::{expand_a, expand_mask, sample_in_ball};
+use crate::util::{B32, B64, ct_eq};
+use crate::{
+    MlDsaError, MlDsaParams, Signature, SigningKey, VerifyingKey, ensure_operational,
+    message_representative,
+};
+
+/// A signing key with `Â`, `ŝ1`, `ŝ2`, and `t̂0` precomputed
+///
+/// Created by [`SigningKey::prepare`].
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct PreparedSigningKey<P: MlDsaParams> {
+    K: B32,
+    tr: B64,
+    a_hat: NttMatrix<P::K, P::L>,
+    s1_hat: NttVector<P::L>,
+    s2_hat: NttVector<P::K>,
+    t0_hat: NttVector<P::K>,
+    #[cfg(feature = "verify-after-sign")]
+    t1_2d_hat: NttVector<P::K>,
+}
+
+impl<P: MlDsaParams> fmt::Debug for PreparedSigningKey<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreparedSigningKey").finish_non_exhaustive()
+    }
+}
+
+impl<P: MlDsaParams> PreparedSigningKey<P> {
+    /// Sign the message `M'`, given as a sequence of byte strings, with the per-signature
+    /// randomness `rnd` (Algorithm 7 ML-DSA.Sign_internal)
+    ///
+    /// See [`PreparedSigningKey::try_sign_mu`] for the errors.
+    pub fn try_sign_internal(&self, mp: &[&[u8]], rnd: &B32) -> Result<Signature<P>, Error> {
+        let mu = message_representative(&self.tr, mp);
+        self.try_sign_mu(&mu, rnd)
+    }
+
+    /// Sign the externally computed message representative `mu = H(tr || M')` with the
+    /// per-signature randomness `rnd` (Algorithm 7 ML-DSA.Sign_internal, from line 7 on)
+    ///
+    /// With the `self-test` feature, returns an error if a self-test has failed.  With the
+    /// `verify-after-sign` feature, returns an error if the signature does not verify under
+    /// the verifying key derived from this key.
+    pub fn try_sign_mu(&self, mu: &B64, rnd: &B32) -> Result<Signature<P>, Error> {
+        ensure_operational()?;
+        let sigma = self.sign_mu_unchecked(mu, rnd);
+
+        #[cfg(feature = "verify-after-sign")]
+        if !verify_mu_with::<P>(&self.a_hat, &self.t1_2d_hat, mu, &sigma) {
+            return Err(MlDsaError::FaultDetected.into());
+        }
+
+        Ok(sigma)
+    }
+
+    // The signing algorithm proper, also used by the self-tests
+    pub(crate) fn sign_mu_unchecked(&self, mu: &B64, rnd: &B32) -> Signature<P> {
+        // Compute the private random seed rho''
+        let rhopp: B64 = H::default()
+            .absorb(&self.K)
+            .absorb(rnd)
+            .absorb(mu)
+            .squeeze_new();
+
+        // Rejection sampling loop
+        for kappa in (0..u16::MAX).step_by(P::L::USIZE) {
+            let y = expand_mask::<P::L, P::Gamma1>(&rhopp, kappa);
+            let w = (&self.a_hat * &y.ntt()).ntt_inverse();
+            let w1 = w.high_bits::<P::TwoGamma2>();
+
+            let w1_tilde = P::encode_w1(&w1);
+            let c_tilde: EncodedCTilde<P> = H::default().absorb(mu).absorb(&w1_tilde).squeeze_new();
+            let c = sample_in_ball(&c_tilde, P::Tau::USIZE);
+            let c_hat = c.ntt();
+
+            let cs1 = (&c_hat * &self.s1_hat).ntt_inverse();
+            let cs2 = (&c_hat * &self.s2_hat).ntt_inverse();
+
+            let z = &y + &cs1;
+            let r0 = (&w - &cs2).low_bits::<P::TwoGamma2>();
+
+            if z.infinity_norm() >= P::GAMMA1_MINUS_BETA
+                || r0.infinity_norm() >= P::GAMMA2_MINUS_BETA
+            {
+                continue;
+            }
+
+            let ct0 = (&c_hat * &self.t0_hat).ntt_inverse();
+            let minus_ct0 = -&ct0;
+            let w_cs2_ct0 = &(&w - &cs2) + &ct0;
+            let h = Hint::<P::K>::new::<P::TwoGamma2>(&minus_ct0, &w_cs2_ct0);
+
+            if ct0.infinity_norm() >= P::Gamma2::U32 || h.hamming_weight() > P::Omega::USIZE {
+                continue;
+            }
+
+            return Signature { c_tilde, z, h };
+        }
+
+        unreachable!("Rejection sampling failed to find a valid signature");
+    }
+
+    /// Sign the message `m` with the context string `ctx`, using fresh randomness from `rng`
+    /// (Algorithm 2 ML-DSA.Sign, hedged variant)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    #[cfg(feature = "rand_core")]
+    pub fn sign_randomized<R: CryptoRng + ?Sized>(
+        &self,
+        m: &[u8],
+        ctx: &[u8],
+        rng: &mut R,
+    ) -> Result<Signature<P>, Error> {
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+
+        let mut rnd = B32::default();
+        rng.fill_bytes(&mut rnd);
+
+        self.try_sign_internal(&[&[0], &[ctx_len], ctx, m], &rnd)
+    }
+
+    /// Sign the message `m` with the context string `ctx`, deterministically (Algorithm 2
+    /// ML-DSA.Sign, deterministic variant with `rnd = 0^32`)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    pub fn sign_deterministic(&self, m: &[u8], ctx: &[u8]) -> Result<Signature<P>, Error> {
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+        self.try_sign_internal(&[&[0], &[ctx_len], ctx, m], &B32::default())
+    }
+}
+
+impl<P: MlDsaParams> SigningKey<P> {
+    /// Precompute the NTT-domain values used by every signature (Algorithm 7
+    /// ML-DSA.Sign_internal, lines 2-5)
+    pub fn prepare(&self) -> PreparedSigningKey<P> {
+        let a_hat = expand_a::<P::K, P::L>(&self.rho);
+
+        // Algorithm 6 lines 5-6, to check signatures against
+        #[cfg(feature = "verify-after-sign")]
+        let t1_2d_hat = {
+            let (t1, _) = compute_t::<P>(&a_hat, &self.s1, &self.s2).power2round();
+            t1_2d_ntt(&t1)
+        };
+
+        PreparedSigningKey {
+            K: self.K,
+            tr: self.tr,
+            a_hat,
+            s1_hat: self.s1.ntt(),
+            s2_hat: self.s2.ntt(),
+            t0_hat: self.t0.ntt(),
+            #[cfg(feature = "verify-after-sign")]
+            t1_2d_hat,
+        }
+    }
+}
+
+impl<P: MlDsaParams> From<&SigningKey<P>> for PreparedSigningKey<P> {
+    fn from(sk: &SigningKey<P>) -> Self {
+        sk.prepare()
+    }
+}
+
+// Â is derived from the public seed rho, so only the secret values are cleared
+#[cfg(feature = "zeroize")]
+impl<P: MlDsaParams> Drop for PreparedSigningKey<P> {
+    fn drop(&mut self) {
+        self.K.zeroize();
+        self.tr.zeroize();
+        self.s1_hat.zeroize();
+        self.s2_hat.zeroize();
+        self.t0_hat.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<P: MlDsaParams> ZeroizeOnDrop for PreparedSigningKey<P> {}
+
+/// Deterministic signing with an empty context string
+impl<P: MlDsaParams> Signer<Signature<P>> for PreparedSigningKey<P> {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<P>, Error> {
+        self.sign_deterministic(msg, &[])
+    }
+}
+
+/// Hedged signing with an empty context string
+#[cfg(feature = "rand_core")]
+impl<P: MlDsaParams> RandomizedSigner<Signature<P>> for PreparedSigningKey<P> {
+    fn try_sign_with_rng<R: TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        msg: &[u8],
+    ) -> Result<Signature<P>, Error> {
+        let mut rnd = B32::default();
+        rng.try_fill_bytes(&mut rnd)
+            .map_err(|_| MlDsaError::RngFailure)?;
+        self.try_sign_internal(&[&[0], &[0], msg], &rnd)
+    }
+}
+
+/// A verifying key with `Â` and `NTT(t1 * 2^d)` precomputed
+///
+/// Created by [`VerifyingKey::prepare`].
+#[derive(Clone, Debug)]
+pub struct PreparedVerifyingKey<P: MlDsaParams> {
+    tr: B64,
+    a_hat: NttMatrix<P::K, P::L>,
+    t1_2d_hat: NttVector<P::K>,
+}
+
+impl<P: MlDsaParams> PreparedVerifyingKey<P> {
+    /// Verify a signature on the message `M'`, given as a sequence of byte strings
+    /// (Algorithm 8 ML-DSA.Verify_internal)
+    ///
+    /// With the `self-test` feature, returns `false` if a self-test has failed.
+    pub fn verify_internal(&self, mp: &[&[u8]], sigma: &Signature<P>) -> bool {
+        let mu = message_representative(&self.tr, mp);
+        self.verify_mu(&mu, sigma)
+    }
+
+    /// Verify a signature on the externally computed message representative
+    /// `mu = H(tr || M')` (Algorithm 8 ML-DSA.Verify_internal, from line 7 on)
+    ///
+    /// With the `self-test` feature, returns `false` if a self-test has failed.
+    pub fn verify_mu(&self, mu: &B64, sigma: &Signature<P>) -> bool {
+        ensure_operational().is_ok() && self.verify_mu_unchecked(mu, sigma)
+    }
+
+    // The verification algorithm proper, also used by the self-tests
+    pub(crate) fn verify_mu_unchecked(&self, mu: &B64, sigma: &Signature<P>) -> bool {
+        verify_mu_with::<P>(&self.a_hat, &self.t1_2d_hat, mu, sigma)
+    }
+
+    /// Verify a signature on the message `m` with the context string `ctx` (Algorithm 3
+    /// ML-DSA.Verify)
+    ///
+    /// Returns an error if the signature is invalid or `ctx` is longer than 255 bytes, or, with
+    /// the `self-test` feature, if a self-test has failed.
+    pub fn verify_with_context(
+        &self,
+        m: &[u8],
+        ctx: &[u8],
+        sigma: &Signature<P>,
+    ) -> Result<(), Error> {
+        ensure_operational()?;
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+
+        if self.verify_internal(&[&[0], &[ctx_len], ctx, m], sigma) {
+            Ok(())
+        } else {
+            Err(MlDsaError::InvalidSignature.into())
+        }
+    }
+}
+
+// NTT(t1 * 2^d) (Algorithm 8 line 9)
+fn t1_2d_ntt<K: ArraySize>(t1: &Vector<K>) -> NttVector<K> {
+    (Elem::new(1 << D) * t1).ntt()
+}
+
+// Algorithm 8 ML-DSA.Verify_internal, from line 7 on, given Â and NTT(t1 * 2^d)
+fn verify_mu_with<P: MlDsaParams>(
+    a_hat: &NttMatrix<P::K, P::L>,
+    t1_2d_hat: &NttVector<P::K>,
+    mu: &B64,
+    sigma: &Signature<P>,
+) -> bool {
+    if sigma.z.infinity_norm() >= P::GAMMA1_MINUS_BETA {
+        return false;
+    }
+
+    // Reconstruct w1 from A*z - c*t1*2^d and the hint
+    let c = sample_in_ball(&sigma.c_tilde, P::Tau::USIZE);
+    let c_hat = c.ntt();
+    let z_hat = sigma.z.ntt();
+    let az_hat = a_hat * &z_hat;
+    let ct1_2d_hat = &c_hat * t1_2d_hat;
+
+    let wp_approx = (&az_hat - &ct1_2d_hat).ntt_inverse();
+    let w1p = sigma.h.use_hint::<P::TwoGamma2>(&wp_approx);
+
+    // Recompute the commitment hash and compare
+    let w1p_tilde = P::encode_w1(&w1p);
+    let cp_tilde: EncodedCTilde<P> = H::default().absorb(mu).absorb(&w1p_tilde).squeeze_new();
+
+    ct_eq(&sigma.c_tilde, &cp_tilde)
+}
+
+impl<P: MlDsaParams> VerifyingKey<P> {
+    /// Precompute the values used by every verification (Algorithm 8
+    /// ML-DSA.Verify_internal, lines 5-6 and part of line 9)
+    pub fn prepare(&self) -> PreparedVerifyingKey<P> {
+        PreparedVerifyingKey {
+            tr: self.tr(),
+            a_hat: expand_a::<P::K, P::L>(&self.rho),
+            t1_2d_hat: t1_2d_ntt(&self.t1),
+        }
+    }
+}
+
+impl<P: MlDsaParams> From<&VerifyingKey<P>> for PreparedVerifyingKey<P> {
+    fn from(vk: &VerifyingKey<P>) -> Self {
+        vk.prepare()
+    }
+}
+
+/// Verification with an empty context string
+impl<P: MlDsaParams> Verifier<Signature<P>> for PreparedVerifyingKey<P> {
+    fn verify(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), Error> {
+        self.verify_with_context(msg, &[], signature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyGen, MlDsa44, MlDsa65, MlDsa87};
+
+    const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    fn prepared_signing<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from([5; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+        let prepared = sk.prepare();
+        assert!(sk.prepared.get().is_none());
+
+        // The same key signs many messages, each exactly as the unprepared key would
+        for i in 0..4u8 {
+            let m = [MSG, &[i]].concat();
+            let sig = prepared.sign_deterministic(&m, b"ctx").unwrap();
+            assert_eq!(sig, sk.sign_deterministic(&m, b"ctx").unwrap());
+            assert!(vk.verify_with_context(&m, b"ctx", &sig).is_ok());
+        }
+
+        // The signing key prepared itself on its first signature
+        assert!(sk.prepared.get().is_some());
+
+        let mu = vk.compute_mu(MSG, &[]).unwrap();
+        let rnd = B32::from([7; 32]);
+        assert_eq!(
+            prepared.try_sign_mu(&mu, &rnd).unwrap(),
+            sk.try_sign_mu(&mu, &rnd).unwrap()
+        );
+        assert_eq!(prepared.sign(MSG), sk.sign(MSG));
+    }
+
+    #[test]
+    fn matches_unprepared() {
+        prepared_signing::<MlDsa44>();
+        prepared_signing::<MlDsa65>();
+        prepared_signing::<MlDsa87>();
+    }
+
+    #[test]
+    fn context_length() {
+        let kp = MlDsa44::key_gen_internal(&B32::from([5; 32])).unwrap();
+        let prepared = PreparedSigningKey::from(kp.signing_key());
+        assert!(prepared.sign_deterministic(MSG, &[0; 255]).is_ok());
+        assert!(prepared.sign_deterministic(MSG, &[0; 256]).is_err());
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn randomized() {
+        let mut rng = rand::rng();
+        let kp = MlDsa65::key_gen(&mut rng).unwrap();
+        let prepared = kp.signing_key().prepare();
+
+        let sig = prepared.sign_with_rng(&mut rng, MSG);
+        assert!(kp.verifying_key().verify(MSG, &sig).is_ok());
+
+        let sig = prepared.sign_randomized(MSG, b"ctx", &mut rng).unwrap();
+        assert!(
+            kp.verifying_key()
+                .verify_with_context(MSG, b"ctx", &sig)
+                .is_ok()
+        );
+    }
+
+    fn prepared_verification<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from([6; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+        let other = P::key_gen_internal(&B32::from([7; 32])).unwrap();
+        let prepared = vk.prepare();
+
+        let sig = sk.sign_deterministic(MSG, b"ctx").unwrap();
+        let enc = sig.encode();
+
+        // Tamper with c~, z, and the hint in turn
+        let mut candidates = vec![sig.clone()];
+        for i in [0, enc.len() / 2, enc.len() - 1] {
+            let mut bad = enc.clone();
+            bad[i] ^= 1;
+            candidates.extend(Signature::<P>::decode(&bad));
+        }
+        candidates.push(other.signing_key().sign_deterministic(MSG, b"ctx").unwrap());
+
+        // z at the bound is rejected before anything is computed
+        let mut big_z = sig.clone();
+        big_z.z.0[0].0[0] = Elem::new(P::GAMMA1_MINUS_BETA);
+        candidates.push(big_z);
+
+        let messages: [(&[u8], &[u8]); 3] = [(MSG, b"ctx"), (MSG, b""), (b"other", b"ctx")];
+        let mut accepted = 0;
+        for sigma in &candidates {
+            for (m, ctx) in messages {
+                let expected = vk.verify_with_context(m, ctx, sigma).is_ok();
+                assert_eq!(
+                    prepared.verify_with_context(m, ctx, sigma).is_ok(),
+                    expected
+                );
+                accepted += usize::from(expected);
+            }
+
+            let mu = vk.compute_mu(MSG, b"ctx").unwrap();
+            assert_eq!(prepared.verify_mu(&mu, sigma), vk.verify_mu(&mu, sigma));
+            assert_eq!(
+                prepared.verify(MSG, sigma).is_ok(),
+                vk.verify(MSG, sigma).is_ok()
+            );
+        }
+
+        // Only the untampered signature on the original message and context is accepted
+        assert_eq!(accepted, 1);
+        assert!(prepared.verify_with_context(MSG, &[0; 256], &sig).is_err());
+    }
+
+    #[test]
+    fn verification_matches_unprepared() {
+        prepared_verification::<MlDsa44>();
+        prepared_verification::<MlDsa65>();
+        prepared_verification::<MlDsa87>();
+    }
+
+    #[cfg(feature = "verify-after-sign")]
+    fn fault_detection<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from([8; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+        let mut prepared = sk.prepare();
+        assert_eq!(prepared.t1_2d_hat, vk.prepare().t1_2d_hat);
+
+        let mu = vk.compute_mu(MSG, &[]).unwrap();
+        let rnd = B32::from([9; 32]);
+        let sig = prepared.try_sign_mu(&mu, &rnd).unwrap();
+        assert_eq!(sig, sk.try_sign_mu(&mu, &rnd).unwrap());
+
+        // A fault in s1 corrupts z = y + c*s1 (Algorithm 7 line 17)
+        let mut s1 = sk.s1.clone();
+        s1.0[0].0[0] = s1.0[0].0[0] + Elem::new(1);
+        prepared.s1_hat = s1.ntt();
+        let err = prepared.try_sign_mu(&mu, &rnd).unwrap_err();
+        assert!(prepared.sign_deterministic(MSG, &[]).is_err());
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            MlDsaError::from_signature_error(&err),
+            Some(MlDsaError::FaultDetected)
+        );
+        #[cfg(not(feature = "alloc"))]
+        let _ = err;
+    }
+
+    #[cfg(feature = "verify-after-sign")]
+    #[test]
+    fn verify_after_sign() {
+        fault_detection::<MlDsa44>();
+        fault_detection::<MlDsa65>();
+        fault_detection::<MlDsa87>();
+    }
+}
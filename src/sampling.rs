@@ -1,7 +1,309 @@
 //! Sampling operations for ML-DSA
+//!
+//! This module implements the pseudorandom sampling algorithms of FIPS 204 Section 7.3: the
+//! rejection samplers that turn SHAKE output into field elements and small coefficients, the
+//! challenge sampler `SampleInBall`, and the `ExpandA`/`ExpandS`/`ExpandMask` functions that
+//! derive the public matrix, the secret vectors, and the signing mask from seeds.  All of them
+//! are generic over the dimensions and ranges, so every parameter set shares one implementation.
 
-/// Random sampling utilities
-pub mod random {
-    /// Random sampling operations
-    pub struct Sampler;
-}
\ No newline at end of file
+use hybrid_array::{
+    Array, ArraySize,
+    typenum::{Len, Length, Unsigned},
+};
+
+use crate::algebra::{
+    BaseField, Elem, Field, Int, NttMatrix, NttPolynomial, NttVector, Polynomial, Truncate, Vector,
+};
+use crate::crypto::{G, H};
+use crate::module_lattice::encode::{Encode, EncodedPolynomialSize, EncodingSize};
+use crate::param::{Eta, SamplingSize};
+
+// Algorithm 13 BytesToBits
+fn bit_set(z: &[u8], i: usize) -> bool {
+    let bit_index = i & 0x07;
+    let byte_index = i >> 3;
+    z[byte_index] & (1 << bit_index) != 0
+}
+
+// Algorithm 14 CoeffFromThreeBytes
+fn coeff_from_three_bytes(b: [u8; 3]) -> Option<Elem> {
+    let b0: Int = b[0].into();
+    let b1: Int = b[1].into();
+    let b2: Int = (b[2] & 0x7f).into();
+
+    let z = (b2 << 16) + (b1 << 8) + b0;
+    (z < BaseField::Q).then_some(Elem::new(z))
+}
+
+// Algorithm 15 CoeffFromHalfByte
+#[allow(clippy::integer_division_remainder_used)]
+fn coeff_from_half_byte(b: u8, eta: Eta) -> Option<Elem> {
+    match eta {
+        Eta::Two if b < 15 => {
+            let b = Int::from(b) % 5;
+            Some(Elem::new(2) - Elem::new(b))
+        }
+        Eta::Four if b < 9 => Some(Elem::new(4) - Elem::new(b.into())),
+        _ => None,
+    }
+}
+
+fn coeffs_from_byte(z: u8, eta: Eta) -> (Option<Elem>, Option<Elem>) {
+    (
+        coeff_from_half_byte(z & 0x0f, eta),
+        coeff_from_half_byte(z >> 4, eta),
+    )
+}
+
+/// Sample a polynomial with `tau` coefficients equal to ±1 and the rest zero, from the
+/// commitment hash `rho` (Algorithm 29 SampleInBall)
+pub fn sample_in_ball(rho: &[u8], tau: usize) -> Polynomial {
+    const ONE: Elem = Elem::new(1);
+    const MINUS_ONE: Elem = Elem::new(BaseField::Q - 1);
+
+    let mut c = Polynomial::default();
+    let mut ctx = H::default().absorb(rho).finalize();
+
+    let mut s = [0u8; 8];
+    ctx.squeeze(&mut s);
+
+    let mut j = [0u8];
+    for i in (256 - tau)..256 {
+        ctx.squeeze(&mut j);
+        while usize::from(j[0]) > i {
+            ctx.squeeze(&mut j);
+        }
+
+        let j = usize::from(j[0]);
+        c.0[i] = c.0[j];
+        c.0[j] = if bit_set(&s, i + tau - 256) {
+            MINUS_ONE
+        } else {
+            ONE
+        };
+    }
+
+    c
+}
+
+// Algorithm 30 RejNTTPoly
+fn rej_ntt_poly(rho: &[u8], r: u8, s: u8) -> NttPolynomial {
+    let mut j = 0;
+    let mut ctx = G::default()
+        .absorb(rho)
+        .absorb(&[s])
+        .absorb(&[r])
+        .finalize();
+
+    let mut a = NttPolynomial::default();
+    let mut s = [0u8; 3];
+    while j < 256 {
+        ctx.squeeze(&mut s);
+        if let Some(x) = coeff_from_three_bytes(s) {
+            a.0[j] = x;
+            j += 1;
+        }
+    }
+
+    a
+}
+
+// Algorithm 31 RejBoundedPoly
+fn rej_bounded_poly(rho: &[u8], eta: Eta, r: u16) -> Polynomial {
+    let mut j = 0;
+    let mut ctx = H::default().absorb(rho).absorb(&r.to_le_bytes()).finalize();
+
+    let mut a = Polynomial::default();
+    let mut z = [0u8];
+    while j < 256 {
+        ctx.squeeze(&mut z);
+        let (z0, z1) = coeffs_from_byte(z[0], eta);
+
+        if let Some(z) = z0 {
+            a.0[j] = z;
+            j += 1;
+        }
+
+        if j == 256 {
+            break;
+        }
+
+        if let Some(z) = z1 {
+            a.0[j] = z;
+            j += 1;
+        }
+    }
+
+    a
+}
+
+/// Expand the public seed `rho` into the `K x L` matrix `Â` in the NTT domain
+/// (Algorithm 32 ExpandA)
+pub fn expand_a<K: ArraySize, L: ArraySize>(rho: &[u8]) -> NttMatrix<K, L> {
+    NttMatrix::new(Array::from_fn(|r| {
+        NttVector::new(Array::from_fn(|s| {
+            rej_ntt_poly(rho, Truncate::truncate(r), Truncate::truncate(s))
+        }))
+    }))
+}
+
+/// Expand the private seed `rho'` into a vector of polynomials with coefficients in
+/// `[-eta, eta]`.  FIPS 204 Algorithm 33 ExpandS produces `s1` with `base = 0` and `s2` with
+/// `base = L`.
+pub fn expand_s<K: ArraySize, Eta: SamplingSize>(rho: &[u8], base: usize) -> Vector<K> {
+    Vector::new(Array::from_fn(|r| {
+        let r = Truncate::truncate(r + base);
+        rej_bounded_poly(rho, Eta::ETA, r)
+    }))
+}
+
+/// Expand the seed `rho''` and counter `mu` into the signing mask `y`, whose coefficients lie in
+/// `(-gamma1, gamma1]` (Algorithm 34 ExpandMask)
+pub fn expand_mask<K, Gamma1>(rho: &[u8], mu: u16) -> Vector<K>
+where
+    K: ArraySize,
+    Gamma1: Unsigned + Len,
+    Length<Gamma1>: EncodingSize,
+{
+    let gamma1 = Elem::new(Gamma1::U32);
+
+    Vector::new(Array::from_fn(|r| {
+        let r: u16 = Truncate::truncate(r);
+        let v = H::default()
+            .absorb(rho)
+            .absorb(&(mu + r).to_le_bytes())
+            .squeeze_new::<EncodedPolynomialSize<Length<Gamma1>>>();
+
+        // BitUnpack(v, gamma1 - 1, gamma1): each packed value z encodes gamma1 - z
+        let z: Polynomial = Encode::<Length<Gamma1>>::decode(&v);
+        Polynomial::new(z.0.iter().map(|&z| gamma1 - z).collect())
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::AlgebraExt;
+    use hybrid_array::typenum::{Shleft, U1, U2, U4, U17, U19};
+
+    // Signed value of a centered field element
+    fn signed(x: Elem) -> i64 {
+        let x = i64::from(x.0);
+        if x <= i64::from(BaseField::Q / 2) {
+            x
+        } else {
+            x - i64::from(BaseField::Q)
+        }
+    }
+
+    #[test]
+    fn three_bytes() {
+        assert_eq!(coeff_from_three_bytes([0, 0, 0]), Some(Elem::new(0)));
+        assert_eq!(coeff_from_three_bytes([1, 2, 3]), Some(Elem::new(0x030201)));
+
+        // The top bit of the last byte is ignored
+        assert_eq!(
+            coeff_from_three_bytes([1, 2, 0x83]),
+            Some(Elem::new(0x030201))
+        );
+
+        // Values at or above q are rejected
+        assert_eq!(coeff_from_three_bytes([1, 0xe0, 0x7f]), None);
+        assert_eq!(coeff_from_three_bytes([1, 0xe0, 0xff]), None);
+        assert_eq!(coeff_from_three_bytes([0xff, 0xff, 0x7f]), None);
+        assert_eq!(
+            coeff_from_three_bytes([0, 0xe0, 0x7f]),
+            Some(Elem::new(BaseField::Q - 1))
+        );
+    }
+
+    #[test]
+    fn half_byte() {
+        for b in 0..16u8 {
+            let two = coeff_from_half_byte(b, Eta::Two).map(signed);
+            let four = coeff_from_half_byte(b, Eta::Four).map(signed);
+
+            if b < 15 {
+                assert_eq!(two, Some(2 - i64::from(b % 5)));
+            } else {
+                assert_eq!(two, None);
+            }
+
+            if b < 9 {
+                assert_eq!(four, Some(4 - i64::from(b)));
+            } else {
+                assert_eq!(four, None);
+            }
+        }
+    }
+
+    #[test]
+    fn sample_in_ball_weight() {
+        for tau in [39, 49, 60] {
+            for seed in 0..8u8 {
+                let c = sample_in_ball(&[seed; 32], tau);
+                let nonzero: Vec<i64> =
+                    c.0.iter().map(|&x| signed(x)).filter(|&x| x != 0).collect();
+
+                assert_eq!(nonzero.len(), tau);
+                assert!(nonzero.iter().all(|&x| x == 1 || x == -1));
+            }
+        }
+    }
+
+    #[test]
+    fn expand_a_is_uniform_and_deterministic() {
+        let rho = [7u8; 32];
+        let a = expand_a::<U2, U4>(&rho);
+        assert_eq!(a, expand_a::<U2, U4>(&rho));
+
+        // Entries are distinct for distinct indices
+        assert_ne!(a.0[0].0[1], a.0[1].0[0]);
+        assert!(
+            a.0.iter()
+                .flat_map(|v| v.0.iter())
+                .all(|p| p.0.iter().all(|x| x.0 < BaseField::Q))
+        );
+    }
+
+    #[test]
+    fn expand_s_is_bounded() {
+        let rho = [3u8; 64];
+
+        let s = expand_s::<U4, U2>(&rho, 0);
+        assert!(s.infinity_norm() <= 2);
+
+        let s = expand_s::<U4, U4>(&rho, 4);
+        assert!(s.infinity_norm() <= 4);
+
+        // The base offset selects different polynomials
+        assert_ne!(expand_s::<U1, U2>(&rho, 0), expand_s::<U1, U2>(&rho, 1));
+        assert_eq!(
+            expand_s::<U2, U2>(&rho, 0).0[1],
+            expand_s::<U1, U2>(&rho, 1).0[0]
+        );
+    }
+
+    #[test]
+    fn expand_mask_is_bounded() {
+        let rho = [5u8; 64];
+
+        let y = expand_mask::<U4, Shleft<U1, U17>>(&rho, 0);
+        assert!(y.0.iter().flat_map(|p| p.0.iter()).all(|&x| {
+            let x = signed(x);
+            x > -(1 << 17) && x <= 1 << 17
+        }));
+
+        let y = expand_mask::<U4, Shleft<U1, U19>>(&rho, 0);
+        assert!(y.0.iter().flat_map(|p| p.0.iter()).all(|&x| {
+            let x = signed(x);
+            x > -(1 << 19) && x <= 1 << 19
+        }));
+
+        // The counter selects different masks
+        assert_eq!(
+            expand_mask::<U2, Shleft<U1, U17>>(&rho, 0).0[1],
+            expand_mask::<U1, Shleft<U1, U17>>(&rho, 1).0[0]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Signing keys stored as their 32-byte seed
+//!
+//! Key generation (Algorithm 6 ML-DSA.KeyGen_internal) is a deterministic function of the seed
+//! `xi`, so a signing key can be stored as those 32 bytes instead of the 2560 to 4896 bytes of
+//! its skEncode form, as the IETF LAMPS drafts recommend.  A [`SeedSigningKey`] expands itself
+//! on first use and keeps the expanded key pair for later operations.
+//!
+//! The expansion hashes the seed, so there is no way back from an expanded [`SigningKey`] to a
+//! seed; the conversion in that direction always fails with [`SeedUnavailable`].
+
+use core::fmt;
+use std::sync::OnceLock;
+
+use signature::{Error, Signer};
+
+#[cfg(feature = "rand_core")]
+use {crate::ensure_operational, rand_core::TryCryptoRng, signature::RandomizedSigner};
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::util::{B32, ct_eq};
+use crate::{KeyGen, KeyPair, MlDsaError, MlDsaParams, Signature, SigningKey, VerifyingKey};
+
+/// An ML-DSA signing key held as its key generation seed `xi`
+///
+/// The expanded key pair is derived on first use and cached.
+#[derive(Clone)]
+pub struct SeedSigningKey<P: MlDsaParams> {
+    xi: B32,
+    expanded: OnceLock<KeyPair<P>>,
+}
+
+impl<P: MlDsaParams> fmt::Debug for SeedSigningKey<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeedSigningKey").finish_non_exhaustive()
+    }
+}
+
+/// Keys are equal if their seeds are, which is checked in constant time
+impl<P: MlDsaParams> PartialEq for SeedSigningKey<P> {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(&self.xi, &other.xi)
+    }
+}
+
+impl<P: MlDsaParams> SeedSigningKey<P> {
+    /// Create a signing key from the seed `xi`
+    ///
+    /// Nothing is computed until the key is first used.
+    pub fn from_seed(xi: B32) -> Self {
+        Self {
+            xi,
+            expanded: OnceLock::new(),
+        }
+    }
+
+    /// Generate a signing key from a fresh random seed (Algorithm 1 ML-DSA.KeyGen, lines 1-4)
+    ///
+    /// Returns an error if `rng` fails, or, with the `self-test` feature, if a self-test has
+    /// failed.  In the latter case no seed is drawn.
+    #[cfg(feature = "rand_core")]
+    pub fn generate<R: TryCryptoRng + ?Sized>(rng: &mut R) -> Result<Self, Error> {
+        ensure_operational()?;
+        let mut xi = B32::default();
+        rng.try_fill_bytes(&mut xi)
+            .map_err(|_| MlDsaError::RngFailure)?;
+        let sk = Self::from_seed(xi);
+
+        #[cfg(feature = "zeroize")]
+        xi.zeroize();
+
+        Ok(sk)
+    }
+
+    /// The seed `xi`
+    pub fn seed(&self) -> &B32 {
+        &self.xi
+    }
+
+    /// Encode the signing key as its 32-byte seed
+    pub fn encode(&self) -> B32 {
+        self.xi
+    }
+
+    /// The key pair derived from the seed (Algorithm 6 ML-DSA.KeyGen_internal), computed on
+    /// first use
+    ///
+    /// With the `self-test` feature, returns an error if a self-test fails.  A failed expansion
+    /// is not cached.
+    pub fn try_key_pair(&self) -> Result<&KeyPair<P>, Error> {
+        if let Some(kp) = self.expanded.get() {
+            return Ok(kp);
+        }
+
+        let kp = P::key_gen_internal(&self.xi)?;
+        Ok(self.expanded.get_or_init(|| kp))
+    }
+
+    /// The expanded signing key, or an error if the expansion fails
+    pub fn try_signing_key(&self) -> Result<&SigningKey<P>, Error> {
+        Ok(self.try_key_pair()?.signing_key())
+    }
+
+    /// The verifying key that corresponds to this signing key, or an error if the expansion
+    /// fails
+    pub fn try_verifying_key(&self) -> Result<&VerifyingKey<P>, Error> {
+        Ok(self.try_key_pair()?.verifying_key())
+    }
+
+    /// Convert into the expanded form, which no longer knows its seed, or return an error if
+    /// the expansion fails
+    ///
+    /// A key that has already been expanded hands over its cached key pair.
+    pub fn try_into_expanded(mut self) -> Result<SigningKey<P>, Error> {
+        let kp = match self.expanded.take() {
+            Some(kp) => kp,
+            None => P::key_gen_internal(&self.xi)?,
+        };
+        Ok(kp.into_parts().0)
+    }
+}
+
+impl<P: MlDsaParams> TryFrom<&[u8]> for SeedSigningKey<P> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let xi = B32::try_from(bytes).map_err(|_| MlDsaError::InvalidLength)?;
+        Ok(Self::from_seed(xi))
+    }
+}
+
+impl<P: MlDsaParams> From<B32> for SeedSigningKey<P> {
+    fn from(xi: B32) -> Self {
+        Self::from_seed(xi)
+    }
+}
+
+impl<P: MlDsaParams> TryFrom<SeedSigningKey<P>> for SigningKey<P> {
+    type Error = Error;
+
+    fn try_from(sk: SeedSigningKey<P>) -> Result<Self, Error> {
+        sk.try_into_expanded()
+    }
+}
+
+/// The error returned when asking for the seed of an expanded signing key
+///
+/// An expanded signing key is derived from its seed by hashing, and does not contain the seed
+/// itself, so the seed cannot be recovered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SeedUnavailable;
+
+impl fmt::Display for SeedUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the seed of an expanded ML-DSA signing key cannot be recovered")
+    }
+}
+
+impl std::error::Error for SeedUnavailable {}
+
+/// Always fails: an expanded signing key does not determine its seed
+impl<P: MlDsaParams> TryFrom<SigningKey<P>> for SeedSigningKey<P> {
+    type Error = SeedUnavailable;
+
+    fn try_from(_: SigningKey<P>) -> Result<Self, SeedUnavailable> {
+        Err(SeedUnavailable)
+    }
+}
+
+/// Always fails: an expanded signing key does not determine its seed
+impl<P: MlDsaParams> TryFrom<&SigningKey<P>> for SeedSigningKey<P> {
+    type Error = SeedUnavailable;
+
+    fn try_from(_: &SigningKey<P>) -> Result<Self, SeedUnavailable> {
+        Err(SeedUnavailable)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<P: MlDsaParams> Drop for SeedSigningKey<P> {
+    fn drop(&mut self) {
+        // The cached key pair zeroizes its signing key when it is dropped
+        self.xi.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<P: MlDsaParams> ZeroizeOnDrop for SeedSigningKey<P> {}
+
+/// Deterministic signing with an empty context string
+impl<P: MlDsaParams> Signer<Signature<P>> for SeedSigningKey<P> {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<P>, Error> {
+        self.try_signing_key()?.try_sign(msg)
+    }
+}
+
+/// Hedged signing with an empty context string
+#[cfg(feature = "rand_core")]
+impl<P: MlDsaParams> RandomizedSigner<Signature<P>> for SeedSigningKey<P> {
+    fn try_sign_with_rng<R: TryCryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+        msg: &[u8],
+    ) -> Result<Signature<P>, Error> {
+        self.try_signing_key()?.try_sign_with_rng(rng, msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MlDsa44, MlDsa65, MlDsa87};
+    use signature::Verifier;
+
+    fn seed_round_trip<P: MlDsaParams>() {
+        let xi = B32::from([0x42; 32]);
+        let sk = SeedSigningKey::<P>::from_seed(xi);
+        assert_eq!(sk.expanded.get(), None);
+
+        // Expansion is the same as key generation from the seed, and happens once
+        let kp = P::key_gen_internal(&xi).unwrap();
+        assert_eq!(sk.try_verifying_key().unwrap(), kp.verifying_key());
+        assert_eq!(sk.expanded.get(), Some(&kp));
+        assert_eq!(sk.try_signing_key().unwrap(), kp.signing_key());
+        assert_eq!(sk.try_key_pair().unwrap(), &kp);
+
+        // The serialized form is just the seed
+        let enc = sk.encode();
+        assert_eq!(enc, xi);
+        let sk2 = SeedSigningKey::<P>::try_from(enc.as_slice()).unwrap();
+        assert_eq!(sk2, sk);
+        assert_eq!(
+            sk2.try_signing_key().unwrap().encode(),
+            kp.signing_key().encode()
+        );
+
+        // Signatures match those of the expanded key
+        let sig = sk.sign(b"message");
+        assert_eq!(sig, kp.signing_key().sign(b"message"));
+        assert!(kp.verifying_key().verify(b"message", &sig).is_ok());
+
+        // Seed to expanded works, whether or not the key was expanded before, and expanded to
+        // seed does not
+        let fresh = SeedSigningKey::<P>::from_seed(xi);
+        assert_eq!(&fresh.try_into_expanded().unwrap(), kp.signing_key());
+        let expanded = SigningKey::try_from(sk2).unwrap();
+        assert_eq!(&expanded, kp.signing_key());
+        assert_eq!(
+            SeedSigningKey::<P>::try_from(&expanded),
+            Err(SeedUnavailable)
+        );
+        assert_eq!(
+            SeedSigningKey::<P>::try_from(expanded),
+            Err(SeedUnavailable)
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        seed_round_trip::<MlDsa44>();
+        seed_round_trip::<MlDsa65>();
+        seed_round_trip::<MlDsa87>();
+    }
+
+    #[test]
+    fn wrong_length() {
+        assert!(SeedSigningKey::<MlDsa44>::try_from(&[0u8; 31][..]).is_err());
+        assert!(SeedSigningKey::<MlDsa44>::try_from(&[0u8; 33][..]).is_err());
+        assert!(SeedSigningKey::<MlDsa44>::try_from(&[0u8; 64][..]).is_err());
+    }
+
+    #[test]
+    fn distinct_seeds() {
+        let a = SeedSigningKey::<MlDsa44>::from_seed(B32::from([1; 32]));
+        let b = SeedSigningKey::<MlDsa44>::from_seed(B32::from([2; 32]));
+        assert_ne!(a, b);
+        assert_ne!(
+            a.try_verifying_key().unwrap(),
+            b.try_verifying_key().unwrap()
+        );
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn generate() {
+        let mut rng = rand::rng();
+        let sk = SeedSigningKey::<MlDsa65>::generate(&mut rng).unwrap();
+        let sig = sk.sign_with_rng(&mut rng, b"message");
+        assert!(
+            sk.try_verifying_key()
+                .unwrap()
+                .verify(b"message", &sig)
+                .is_ok()
+        );
+    }
+}
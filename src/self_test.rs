@@ -0,0 +1,229 @@
+//! Power-up and conditional self-tests
+//!
+//! With the `self-test` feature, the known-answer tests in this module run once, before the
+//! first key generation, signature, or verification, and their result is cached for the life
+//! of the process.  The tests cover SHAKE128 and SHAKE256, and ML-DSA-44 KeyGen, Sign, and
+//! Verify.  Every freshly generated key pair also goes through a pairwise consistency test: a
+//! signature made with the new signing key must verify under the new verifying key.
+//!
+//! If any test fails, the module enters an error state that it never leaves.  In that state,
+//! key generation, signing, and verification return an error, and the verification functions
+//! that return a `bool` return `false`.  No operation panics because of the error state.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::crypto::{G, H};
+use crate::error::MlDsaError;
+use crate::util::{B32, B64};
+use crate::{KeyPair, MlDsa44, MlDsaParams, key_gen_unchecked, message_representative};
+use hex_literal::hex;
+use sha3::{Digest, Sha3_256};
+
+/// The state of the self-tests
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The known-answer tests have not run yet
+    Untested,
+    /// The known-answer tests passed, and no pairwise consistency test has failed
+    Operational,
+    /// A self-test failed; key generation, signing, and verification return an error
+    Error,
+}
+
+const UNTESTED: u8 = 0;
+const OPERATIONAL: u8 = 1;
+const ERROR: u8 = 2;
+
+struct SelfTestState(AtomicU8);
+
+impl SelfTestState {
+    const fn new() -> Self {
+        Self(AtomicU8::new(UNTESTED))
+    }
+
+    fn status(&self) -> Status {
+        match self.0.load(Ordering::Acquire) {
+            UNTESTED => Status::Untested,
+            OPERATIONAL => Status::Operational,
+            _ => Status::Error,
+        }
+    }
+
+    // Run `tests` if they have not run yet.  Concurrent first callers may each run them; the
+    // result is the same, and an error state recorded in the meantime is never overwritten.
+    fn ensure(&self, tests: impl FnOnce() -> bool) -> Result<(), MlDsaError> {
+        if self.status() == Status::Untested {
+            let result = if tests() { OPERATIONAL } else { ERROR };
+            let _ = self
+                .0
+                .compare_exchange(UNTESTED, result, Ordering::AcqRel, Ordering::Acquire);
+        }
+
+        match self.status() {
+            Status::Operational => Ok(()),
+            _ => Err(MlDsaError::SelfTestFailure),
+        }
+    }
+
+    fn fail(&self) {
+        self.0.store(ERROR, Ordering::Release);
+    }
+}
+
+static STATE: SelfTestState = SelfTestState::new();
+
+/// Run the known-answer tests now, if they have not run yet
+///
+/// Calling this is optional: the tests run automatically before the first operation.  Returns
+/// an error if the module is in the error state.
+pub fn run() -> Result<(), MlDsaError> {
+    STATE.ensure(known_answer_tests)
+}
+
+/// The current state of the self-tests
+pub fn status() -> Status {
+    STATE.status()
+}
+
+pub(crate) fn ensure() -> Result<(), MlDsaError> {
+    run()
+}
+
+// Run the pairwise consistency test on a new key pair, and enter the error state if it fails
+pub(crate) fn check_key_pair<P: MlDsaParams>(kp: &KeyPair<P>) -> Result<(), MlDsaError> {
+    if pairwise_consistent(kp) {
+        Ok(())
+    } else {
+        STATE.fail();
+        Err(MlDsaError::SelfTestFailure)
+    }
+}
+
+const PCT_MSG: &[u8] = b"ML-DSA pairwise consistency test";
+
+// Sign with the signing key, verify with the verifying key.  Each side computes mu from its
+// own copy of tr, so a mismatched tr fails the test too.
+fn pairwise_consistent<P: MlDsaParams>(kp: &KeyPair<P>) -> bool {
+    let mp: &[&[u8]] = &[&[0], &[0], PCT_MSG];
+    let sig_mu = message_representative(&kp.signing_key.tr, mp);
+    let vfy_mu = message_representative(&kp.verifying_key.tr(), mp);
+
+    let sig = kp
+        .signing_key
+        .prepare()
+        .sign_mu_unchecked(&sig_mu, &B32::default());
+    kp.verifying_key
+        .prepare()
+        .verify_mu_unchecked(&vfy_mu, &sig)
+}
+
+// The known-answer vectors are SHA3-256 digests of the expected outputs, generated with
+// xi = 00 01 .. 1f and the message below, signed deterministically with an empty context
+const KAT_SEED: [u8; 32] = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+const KAT_MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+const KAT_PK: [u8; 32] = hex!("373c7bf2cac5bd2a6c35933bab0fa1c951f22247e1333383fcb618822080373f");
+const KAT_SK: [u8; 32] = hex!("da0665a6d807669d06aeb7010507aa6bd4640641055462e38e37344a274f36a6");
+const KAT_SIG: [u8; 32] = hex!("8c320870c58c4283a268d94f625e347b4766e1424c2e2f200965a83dd46cd252");
+
+// The first 32 bytes of SHAKE128("abc") and SHAKE256("abc")
+const KAT_SHAKE128: [u8; 32] =
+    hex!("5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8");
+const KAT_SHAKE256: [u8; 32] =
+    hex!("483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739");
+
+fn digest_matches(data: &[u8], expected: &[u8; 32]) -> bool {
+    Sha3_256::digest(data).as_slice() == expected
+}
+
+fn shake_tests() -> bool {
+    let shake128: B32 = G::default().absorb(b"abc").squeeze_new();
+    let shake256: B32 = H::default().absorb(b"abc").squeeze_new();
+    shake128.as_slice() == KAT_SHAKE128 && shake256.as_slice() == KAT_SHAKE256
+}
+
+fn known_answer_tests() -> bool {
+    if !shake_tests() {
+        return false;
+    }
+
+    // KeyGen
+    let kp = key_gen_unchecked::<MlDsa44>(&B32::from(KAT_SEED));
+    let (sk, vk) = (&kp.signing_key, &kp.verifying_key);
+    if !digest_matches(&vk.encode(), &KAT_PK) || !digest_matches(&sk.encode(), &KAT_SK) {
+        return false;
+    }
+
+    // Sign
+    let mu: B64 = message_representative(&sk.tr, &[&[0], &[0], KAT_MSG]);
+    let sig = sk.prepare().sign_mu_unchecked(&mu, &B32::default());
+    let enc = sig.encode();
+    if !digest_matches(&enc, &KAT_SIG) {
+        return false;
+    }
+
+    // Verify: the signature is accepted, and a copy with a corrupted c~ is rejected
+    let vk = vk.prepare();
+    let mut bad = sig.clone();
+    bad.c_tilde[0] ^= 1;
+    vk.verify_mu_unchecked(&mu, &sig) && !vk.verify_mu_unchecked(&mu, &bad)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyGen, MlDsa65, MlDsa87};
+
+    #[test]
+    fn known_answers() {
+        assert!(shake_tests());
+        assert!(known_answer_tests());
+        assert_eq!(run(), Ok(()));
+        assert_eq!(status(), Status::Operational);
+    }
+
+    #[test]
+    fn state_transitions() {
+        let state = SelfTestState::new();
+        assert_eq!(state.status(), Status::Untested);
+        assert!(state.ensure(|| true).is_ok());
+        assert_eq!(state.status(), Status::Operational);
+
+        // The tests run only once
+        assert!(state.ensure(|| unreachable!()).is_ok());
+
+        // A later failure is permanent
+        state.fail();
+        assert_eq!(state.status(), Status::Error);
+        assert!(state.ensure(|| true).is_err());
+
+        let state = SelfTestState::new();
+        assert!(state.ensure(|| false).is_err());
+        assert_eq!(state.status(), Status::Error);
+        assert!(state.ensure(|| true).is_err());
+    }
+
+    fn consistency<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from([3; 32])).unwrap();
+        assert!(pairwise_consistent(&kp));
+
+        // A verifying key from a different key pair
+        let other = P::key_gen_internal(&B32::from([4; 32])).unwrap();
+        let mixed = KeyPair {
+            signing_key: kp.signing_key.clone(),
+            verifying_key: other.verifying_key.clone(),
+        };
+        assert!(!pairwise_consistent(&mixed));
+
+        // A signing key with a corrupted tr
+        let mut bad_tr = kp.clone();
+        bad_tr.signing_key.tr[0] ^= 1;
+        assert!(!pairwise_consistent(&bad_tr));
+    }
+
+    #[test]
+    fn pairwise_consistency() {
+        consistency::<MlDsa44>();
+        consistency::<MlDsa65>();
+        consistency::<MlDsa87>();
+    }
+}
@@ -0,0 +1,252 @@
+//! Incremental signing and verification of long messages
+//!
+//! ML-DSA only ever uses the message through the message representative
+//! `mu = H(tr || 0 || |ctx| || ctx || M)`, which is a single SHAKE256 computation.  The types in
+//! this module absorb the message a chunk at a time into that computation, so a message never
+//! has to be held in memory as a whole.  The same machinery backs the `signature` crate's
+//! [`MultipartSigner`] and [`MultipartVerifier`] implementations.
+
+use core::mem;
+use signature::{Error, MultipartSigner, MultipartVerifier};
+
+#[cfg(feature = "rand_core")]
+use rand_core::CryptoRng;
+
+use crate::crypto::H;
+use crate::util::B64;
+use crate::{MlDsaError, MlDsaParams, Signature, SigningKey, VerifyingKey, ensure_operational};
+
+// The message representative, computed incrementally
+pub(crate) struct MuHasher(H);
+
+impl MuHasher {
+    // Absorb tr and the pure ML-DSA prefix 0 || |ctx| || ctx
+    pub(crate) fn new(tr: &B64, ctx: &[u8]) -> Result<Self, Error> {
+        ensure_operational()?;
+        let ctx_len = u8::try_from(ctx.len()).map_err(|_| MlDsaError::ContextTooLong)?;
+        let h = H::default().absorb(tr).absorb(&[0, ctx_len]).absorb(ctx);
+        Ok(Self(h))
+    }
+
+    pub(crate) fn update(&mut self, chunk: &[u8]) {
+        self.0 = mem::take(&mut self.0).absorb(chunk);
+    }
+
+    pub(crate) fn finish(self) -> B64 {
+        self.0.squeeze_new()
+    }
+}
+
+/// An in-progress signature over a message that is supplied in chunks
+///
+/// Created by [`SigningKey::streaming_signer`].
+pub struct StreamingSigner<'a, P: MlDsaParams> {
+    key: &'a SigningKey<P>,
+    mu: MuHasher,
+}
+
+impl<P: MlDsaParams> StreamingSigner<'_, P> {
+    /// Append `chunk` to the message
+    pub fn update(&mut self, chunk: &[u8]) -> &mut Self {
+        self.mu.update(chunk);
+        self
+    }
+
+    /// Sign the message, deterministically
+    ///
+    /// Returns an error under the same conditions as [`SigningKey::try_sign_mu`].
+    pub fn finish_deterministic(self) -> Result<Signature<P>, Error> {
+        self.key.sign_mu_deterministic(&self.mu.finish())
+    }
+
+    /// Sign the message, using fresh randomness from `rng`
+    ///
+    /// Returns an error under the same conditions as [`SigningKey::try_sign_mu`].
+    #[cfg(feature = "rand_core")]
+    pub fn finish_randomized<R: CryptoRng + ?Sized>(
+        self,
+        rng: &mut R,
+    ) -> Result<Signature<P>, Error> {
+        self.key.sign_mu_randomized(&self.mu.finish(), rng)
+    }
+}
+
+/// An in-progress verification of a message that is supplied in chunks
+///
+/// Created by [`VerifyingKey::streaming_verifier`].
+pub struct StreamingVerifier<'a, P: MlDsaParams> {
+    key: &'a VerifyingKey<P>,
+    mu: MuHasher,
+}
+
+impl<P: MlDsaParams> StreamingVerifier<'_, P> {
+    /// Append `chunk` to the message
+    pub fn update(&mut self, chunk: &[u8]) -> &mut Self {
+        self.mu.update(chunk);
+        self
+    }
+
+    /// Verify `sigma` on the message
+    ///
+    /// Returns an error if the signature is invalid, or, with the `self-test` feature, if a
+    /// self-test has failed.
+    pub fn finish(self, sigma: &Signature<P>) -> Result<(), Error> {
+        ensure_operational()?;
+        if self.key.verify_mu(&self.mu.finish(), sigma) {
+            Ok(())
+        } else {
+            Err(MlDsaError::InvalidSignature.into())
+        }
+    }
+}
+
+impl<P: MlDsaParams> SigningKey<P> {
+    /// Start signing a message with the context string `ctx`, to be supplied in chunks
+    /// (Algorithm 2 ML-DSA.Sign, with the message hashed incrementally)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    pub fn streaming_signer(&self, ctx: &[u8]) -> Result<StreamingSigner<'_, P>, Error> {
+        Ok(StreamingSigner {
+            key: self,
+            mu: MuHasher::new(&self.tr, ctx)?,
+        })
+    }
+}
+
+impl<P: MlDsaParams> VerifyingKey<P> {
+    /// Start verifying a signature on a message with the context string `ctx`, to be supplied
+    /// in chunks (Algorithm 3 ML-DSA.Verify, with the message hashed incrementally)
+    ///
+    /// Returns an error if `ctx` is longer than 255 bytes.
+    pub fn streaming_verifier(&self, ctx: &[u8]) -> Result<StreamingVerifier<'_, P>, Error> {
+        Ok(StreamingVerifier {
+            key: self,
+            mu: MuHasher::new(&self.tr(), ctx)?,
+        })
+    }
+}
+
+/// Deterministic signing of a message given as a sequence of chunks, with an empty context
+impl<P: MlDsaParams> MultipartSigner<Signature<P>> for SigningKey<P> {
+    fn try_multipart_sign(&self, msg: &[&[u8]]) -> Result<Signature<P>, Error> {
+        let mut signer = self.streaming_signer(&[])?;
+        for chunk in msg {
+            signer.update(chunk);
+        }
+        signer.finish_deterministic()
+    }
+}
+
+/// Verification of a message given as a sequence of chunks, with an empty context
+impl<P: MlDsaParams> MultipartVerifier<Signature<P>> for VerifyingKey<P> {
+    fn multipart_verify(&self, msg: &[&[u8]], signature: &Signature<P>) -> Result<(), Error> {
+        let mut verifier = self.streaming_verifier(&[])?;
+        for chunk in msg {
+            verifier.update(chunk);
+        }
+        verifier.finish(signature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::B32;
+    use crate::{KeyGen, MlDsa44, MlDsa65, MlDsa87};
+
+    const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    fn streaming<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from([3; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+        let expected = sk.sign_deterministic(MSG, b"ctx").unwrap();
+
+        // Any split of the message gives the same signature as one-shot signing
+        for split in [0, 1, 20, MSG.len()] {
+            let (a, b) = MSG.split_at(split);
+            let mut signer = sk.streaming_signer(b"ctx").unwrap();
+            signer.update(a).update(b);
+            assert_eq!(signer.finish_deterministic().unwrap(), expected);
+
+            let mut verifier = vk.streaming_verifier(b"ctx").unwrap();
+            verifier.update(a).update(b);
+            assert!(verifier.finish(&expected).is_ok());
+        }
+
+        // A different message or context fails
+        let mut verifier = vk.streaming_verifier(b"ctx").unwrap();
+        verifier.update(MSG).update(b"!");
+        assert!(verifier.finish(&expected).is_err());
+
+        let mut verifier = vk.streaming_verifier(b"").unwrap();
+        verifier.update(MSG);
+        assert!(verifier.finish(&expected).is_err());
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        streaming::<MlDsa44>();
+        streaming::<MlDsa65>();
+        streaming::<MlDsa87>();
+    }
+
+    #[test]
+    fn context_length() {
+        let kp = MlDsa44::key_gen_internal(&B32::from([3; 32])).unwrap();
+        assert!(kp.signing_key().streaming_signer(&[0; 255]).is_ok());
+        assert!(kp.signing_key().streaming_signer(&[0; 256]).is_err());
+        assert!(kp.verifying_key().streaming_verifier(&[0; 255]).is_ok());
+        assert!(kp.verifying_key().streaming_verifier(&[0; 256]).is_err());
+    }
+
+    #[test]
+    fn multipart() {
+        let kp = MlDsa65::key_gen_internal(&B32::from([4; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        let sig = sk.multipart_sign(&[b"The quick brown fox ", b"", b"jumps over the lazy dog"]);
+        assert_eq!(sig, sk.sign_deterministic(MSG, &[]).unwrap());
+        assert!(vk.multipart_verify(&[MSG], &sig).is_ok());
+        assert!(vk.multipart_verify(&[&MSG[..10], &MSG[10..]], &sig).is_ok());
+        assert!(vk.multipart_verify(&[MSG, MSG], &sig).is_err());
+        assert!(vk.multipart_verify(&[], &sig).is_err());
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn streaming_randomized() {
+        let mut rng = rand::rng();
+        let kp = MlDsa44::key_gen(&mut rng).unwrap();
+
+        let mut signer = kp.signing_key().streaming_signer(&[]).unwrap();
+        signer.update(MSG);
+        let sig = signer.finish_randomized(&mut rng).unwrap();
+        assert!(
+            kp.verifying_key()
+                .verify_with_context(MSG, &[], &sig)
+                .is_ok()
+        );
+    }
+
+    #[cfg(feature = "verify-after-sign")]
+    #[test]
+    fn streaming_fault() {
+        use crate::algebra::Elem;
+
+        // A fault in s1 corrupts z = y + c*s1, and the signature no longer verifies
+        let kp = MlDsa44::key_gen_internal(&B32::from([5; 32])).unwrap();
+        let mut sk = kp.signing_key().clone();
+        sk.s1.0[0].0[0] = sk.s1.0[0].0[0] + Elem::new(1);
+
+        let mut signer = sk.streaming_signer(&[]).unwrap();
+        signer.update(MSG);
+        let err = signer.finish_deterministic().unwrap_err();
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            MlDsaError::from_signature_error(&err),
+            Some(MlDsaError::FaultDetected)
+        );
+        #[cfg(not(feature = "alloc"))]
+        let _ = err;
+    }
+}
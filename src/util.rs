@@ -1,7 +1,38 @@
 //! Utility functions for ML-DSA
 
-/// Common utility functions
-pub mod common {
-    /// Utility operations
-    pub struct Util;
-}
\ No newline at end of file
+use hybrid_array::{
+    Array,
+    typenum::{U32, U64},
+};
+
+/// A 32-byte array, used for seeds such as `xi`, `rho`, `K`, and `rnd`
+pub type B32 = Array<u8, U32>;
+
+/// A 64-byte array, used for values such as `rho'`, `tr`, and `mu`
+pub type B64 = Array<u8, U64>;
+
+/// Compare two byte strings without branching on their contents
+///
+/// The running time depends only on the lengths of the inputs, so comparing a received value
+/// against a recomputed one does not leak how many leading bytes matched.
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn constant_time_eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"abc", b"abc"));
+        assert!(!ct_eq(b"abc", b"abd"));
+        assert!(!ct_eq(b"abc", b"ab"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Consistency checks for imported keys
+//!
+//! An expanded signing key carries values that are all derived from `rho`, `s1`, and `s2`: the
+//! low-order part `t0` of `t = A * s1 + s2`, and the public key hash `tr`.  Decoding a key does
+//! not check those relations, so a corrupted or maliciously crafted key would decode fine and
+//! then produce signatures that never verify.  The checks in this module recompute the derived
+//! values (Algorithm 6 ML-DSA.KeyGen_internal, lines 5-9) and compare them to the stored ones,
+//! as key import tests in the style of FIPS 140-3 require.
+
+use core::fmt;
+use hybrid_array::typenum::Unsigned;
+
+use crate::algebra::AlgebraExt;
+use crate::crypto::H;
+use crate::param::EncodedSigningKey;
+use crate::sampling::expand_a;
+use crate::util::{B64, ct_eq};
+use crate::{KeyPair, MlDsaParams, SigningKey, VerifyingKey, compute_t};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The reason an imported key was rejected
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyValidationError {
+    /// The encoding does not have the length of a signing key for the parameter set
+    InvalidLength,
+    /// A coefficient of `s1` lies outside `[-eta, eta]`
+    S1OutOfRange,
+    /// A coefficient of `s2` lies outside `[-eta, eta]`
+    S2OutOfRange,
+    /// `t0` is not the low-order part of `t` recomputed from `rho`, `s1`, and `s2`
+    T0Mismatch,
+    /// `tr` is not the hash of the public key recomputed from `rho`, `s1`, and `s2`
+    TrMismatch,
+    /// The verifying key is not the one recomputed from `rho`, `s1`, and `s2`
+    VerifyingKeyMismatch,
+}
+
+impl fmt::Display for KeyValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidLength => "invalid signing key length",
+            Self::S1OutOfRange => "s1 coefficient out of range",
+            Self::S2OutOfRange => "s2 coefficient out of range",
+            Self::T0Mismatch => "t0 does not match rho, s1 and s2",
+            Self::TrMismatch => "tr does not match the public key",
+            Self::VerifyingKeyMismatch => "verifying key does not match the signing key",
+        })
+    }
+}
+
+impl std::error::Error for KeyValidationError {}
+
+impl<P: MlDsaParams> SigningKey<P> {
+    /// Decode a signing key from bytes and check that it is consistent (Algorithm 25 skDecode,
+    /// followed by [`SigningKey::validate`])
+    pub fn import(bytes: &[u8]) -> Result<Self, KeyValidationError> {
+        #[allow(unused_mut)]
+        let mut enc = EncodedSigningKey::<P>::try_from(bytes)
+            .map_err(|_| KeyValidationError::InvalidLength)?;
+
+        // Every bit pattern of t0 is in its packed range, so decoding fails only if s1 or s2
+        // is out of range
+        let sk = Self::decode(&enc).ok_or_else(|| {
+            let (_, _, _, s1, _, _) = P::split_sk(&enc);
+            #[allow(unused_mut)]
+            let mut s1 = P::decode_s1(s1);
+            let err = match s1 {
+                Some(_) => KeyValidationError::S2OutOfRange,
+                None => KeyValidationError::S1OutOfRange,
+            };
+
+            #[cfg(feature = "zeroize")]
+            s1.zeroize();
+
+            err
+        });
+
+        #[cfg(feature = "zeroize")]
+        enc.zeroize();
+
+        let sk = sk?;
+        sk.validate()?;
+        Ok(sk)
+    }
+
+    /// Check that the signing key is internally consistent
+    ///
+    /// Checks that `s1` and `s2` are in range, then recomputes `t` from `rho`, `s1`, and `s2`
+    /// and checks that `t0` and `tr` match it.  Returns the first check that fails.
+    pub fn validate(&self) -> Result<(), KeyValidationError> {
+        self.derive_verifying_key().map(|_| ())
+    }
+
+    /// Check that the signing key is internally consistent, and that `vk` is its verifying
+    /// key
+    pub fn validate_verifying_key(&self, vk: &VerifyingKey<P>) -> Result<(), KeyValidationError> {
+        let expected = self.derive_verifying_key()?;
+        let matches = ct_eq(&expected.encode(), &vk.encode());
+        matches
+            .then_some(())
+            .ok_or(KeyValidationError::VerifyingKeyMismatch)
+    }
+
+    // Algorithm 6 lines 5-9, checked against the stored t0 and tr
+    fn derive_verifying_key(&self) -> Result<VerifyingKey<P>, KeyValidationError> {
+        let eta = P::Eta::U32;
+        if self.s1.infinity_norm() > eta {
+            return Err(KeyValidationError::S1OutOfRange);
+        }
+        if self.s2.infinity_norm() > eta {
+            return Err(KeyValidationError::S2OutOfRange);
+        }
+
+        let a_hat = expand_a::<P::K, P::L>(&self.rho);
+        let t = compute_t::<P>(&a_hat, &self.s1, &self.s2);
+        let (t1, t0) = t.power2round();
+
+        let t0_matches = ct_eq(&P::encode_t0(&t0), &P::encode_t0(&self.t0));
+        if !t0_matches {
+            return Err(KeyValidationError::T0Mismatch);
+        }
+
+        let vk = VerifyingKey { rho: self.rho, t1 };
+        let tr: B64 = H::default().absorb(&vk.encode()).squeeze_new();
+        if !ct_eq(&tr, &self.tr) {
+            return Err(KeyValidationError::TrMismatch);
+        }
+
+        Ok(vk)
+    }
+}
+
+impl<P: MlDsaParams> KeyPair<P> {
+    /// Combine a signing key and a verifying key into a key pair, checking that they are
+    /// consistent with each other
+    pub fn from_parts(
+        signing_key: SigningKey<P>,
+        verifying_key: VerifyingKey<P>,
+    ) -> Result<Self, KeyValidationError> {
+        signing_key.validate_verifying_key(&verifying_key)?;
+        Ok(Self {
+            signing_key,
+            verifying_key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::Elem;
+    use crate::param::SigningKeyParams;
+    use crate::util::B32;
+    use crate::{KeyGen, MlDsa44, MlDsa65, MlDsa87};
+
+    fn valid_keys<P: MlDsaParams>() {
+        let kp = P::key_gen_internal(&B32::from([9; 32])).unwrap();
+        let (sk, vk) = (kp.signing_key(), kp.verifying_key());
+
+        assert_eq!(sk.validate(), Ok(()));
+        assert_eq!(sk.validate_verifying_key(vk), Ok(()));
+        assert_eq!(SigningKey::<P>::import(&sk.encode()).as_ref(), Ok(sk));
+        assert_eq!(
+            KeyPair::from_parts(sk.clone(), vk.clone()).as_ref(),
+            Ok(&kp)
+        );
+
+        // A key pair made of two different keys
+        let other = P::key_gen_internal(&B32::from([10; 32])).unwrap();
+        assert_eq!(
+            sk.validate_verifying_key(other.verifying_key()),
+            Err(KeyValidationError::VerifyingKeyMismatch)
+        );
+        assert_eq!(
+            KeyPair::from_parts(sk.clone(), other.verifying_key().clone()),
+            Err(KeyValidationError::VerifyingKeyMismatch)
+        );
+    }
+
+    #[test]
+    fn valid() {
+        valid_keys::<MlDsa44>();
+        valid_keys::<MlDsa65>();
+        valid_keys::<MlDsa87>();
+    }
+
+    #[test]
+    fn corrupted_encodings() {
+        let kp = MlDsa44::key_gen_internal(&B32::from([9; 32])).unwrap();
+        let enc = kp.signing_key().encode();
+        let s1_start = 128;
+        let s2_start = s1_start + <MlDsa44 as SigningKeyParams>::S1Size::USIZE;
+        let t0_start = s2_start + <MlDsa44 as SigningKeyParams>::S2Size::USIZE;
+
+        let import = |bytes: &[u8]| SigningKey::<MlDsa44>::import(bytes).map(|_| ());
+        assert_eq!(import(&enc[1..]), Err(KeyValidationError::InvalidLength));
+
+        // eta = 2: encoded values above 4 are out of range
+        let mut bad = enc;
+        bad[s1_start] |= 0x07;
+        assert_eq!(import(&bad), Err(KeyValidationError::S1OutOfRange));
+
+        let mut bad = enc;
+        bad[s2_start] |= 0x07;
+        assert_eq!(import(&bad), Err(KeyValidationError::S2OutOfRange));
+
+        // In-range changes to s1, s2, t0, or rho break the relation between them
+        let change_in_range = |b: u8| if b & 0x07 == 4 { b ^ 0x07 } else { b ^ 0x01 };
+
+        let mut bad = enc;
+        bad[s1_start] = change_in_range(bad[s1_start]);
+        assert_eq!(import(&bad), Err(KeyValidationError::T0Mismatch));
+
+        let mut bad = enc;
+        bad[s2_start] = change_in_range(bad[s2_start]);
+        assert_eq!(import(&bad), Err(KeyValidationError::T0Mismatch));
+
+        let mut bad = enc;
+        bad[t0_start + 100] ^= 0x10;
+        assert_eq!(import(&bad), Err(KeyValidationError::T0Mismatch));
+
+        let mut bad = enc;
+        bad[0] ^= 0x01;
+        assert_eq!(import(&bad), Err(KeyValidationError::T0Mismatch));
+
+        // tr is only checked against the public key
+        let mut bad = enc;
+        bad[64] ^= 0x01;
+        assert_eq!(import(&bad), Err(KeyValidationError::TrMismatch));
+
+        // K is random, so any value is consistent
+        let mut bad = enc;
+        bad[32] ^= 0x01;
+        assert_eq!(import(&bad), Ok(()));
+    }
+
+    #[test]
+    fn out_of_range_in_memory() {
+        // Only decoding can produce keys today, but validation does not rely on that
+        let kp = MlDsa87::key_gen_internal(&B32::from([9; 32])).unwrap();
+        let mut sk = kp.signing_key().clone();
+        sk.s2.0[3].0[255] = Elem::new(3);
+        assert_eq!(sk.validate(), Err(KeyValidationError::S2OutOfRange));
+        sk.s1.0[0].0[0] = -Elem::new(3);
+        assert_eq!(sk.validate(), Err(KeyValidationError::S1OutOfRange));
+
+        // ML-DSA-65 has eta = 4
+        let kp = MlDsa65::key_gen_internal(&B32::from([9; 32])).unwrap();
+        let mut sk = kp.signing_key().clone();
+        let four = Elem::new(4);
+        sk.s1.0[0].0[0] = if sk.s1.0[0].0[0] == four { -four } else { four };
+        assert_eq!(sk.validate(), Err(KeyValidationError::T0Mismatch));
+        sk.s1.0[0].0[0] = Elem::new(5);
+        assert_eq!(sk.validate(), Err(KeyValidationError::S1OutOfRange));
+    }
+}